@@ -5,11 +5,23 @@ use crate::{
 		pagination::{PaginatedResponse, PaginationLinks, PaginationParams},
 	},
 };
-use selendra_db::{db::SortOrder, models::account::SubstrateAccount};
-use selendra_rust_client::utils::validate_ss58_address;
+use selendra_db::{
+	db::{GenericDB, SortOrder},
+	models::{
+		account::{AccountLink, EvmAccount, SubstrateAccount},
+		extrinsic::SubstrateExtrinsic,
+		transfer::SubstrateTransfer,
+	},
+};
+use selendra_rust_client::{
+	address::{evm_to_substrate_address, normalize_ss58_address, ChainAddress},
+	SubstrateClient,
+};
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use utoipa::ToSchema;
 use validator::Validate;
 
@@ -38,13 +50,13 @@ pub async fn get_account_by_address(
 	data: web::Data<AppState>,
 	query: web::Query<AddressQuery>,
 ) -> impl Responder {
-	let address = query.address.clone();
-
-	// Validate address format
-	if !validate_ss58_address(&address) {
-		return HttpResponse::BadRequest()
-			.json(ErrorResponse { message: "Invalid substrate address format".to_string() });
-	}
+	// Stored addresses use Selendra's prefix, whatever network the caller encoded for
+	let address = match normalize_ss58_address(&query.address) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid substrate address format".to_string() }),
+	};
 
 	let db = data.surreal_db.setup_account_db().await;
 
@@ -61,6 +73,335 @@ pub async fn get_account_by_address(
 	}
 }
 
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct LinkedAccount {
+	/// Substrate address
+	pub substrate_address: String,
+	/// EVM address linked to the Substrate address, if any
+	pub evm_address: Option<String>,
+	/// Whether the link is a claim rather than the hashed address mapping
+	pub claimed: bool,
+	/// Native balances
+	pub native: Option<SubstrateAccount>,
+	/// EVM balance and activity
+	pub evm: Option<EvmAccount>,
+}
+
+/// Get the native and EVM sides of an account by either address
+#[utoipa::path(
+    get,
+    path = "/accounts/linked",
+    params(
+        ("address" = String, Query, description = "Substrate or EVM address", example = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+    ),
+    responses(
+        (status = 200, description = "Linked account", body = LinkedAccount),
+        (status = 400, description = "Invalid address format", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "accounts"
+)]
+pub async fn get_linked_account(
+	data: web::Data<AppState>,
+	query: web::Query<AddressQuery>,
+) -> impl Responder {
+	let link_db = data.surreal_db.setup_account_link_db().await;
+
+	let link = match ChainAddress::parse(&query.address) {
+		Ok(address @ ChainAddress::Evm(_)) => {
+			let evm_address = address.canonical();
+			link_db.get_item(&format!("link_{}", evm_address)).await.map(|link| match link {
+				Some(link) => (link.substrate_address, Some(evm_address), link.claimed),
+				// Not indexed yet, the hashed mapping still applies
+				None => (
+					evm_to_substrate_address(&evm_address).unwrap_or_default(),
+					Some(evm_address),
+					false,
+				),
+			})
+		},
+		Ok(address) => {
+			let substrate_address = address.canonical();
+			find_evm_link(&link_db, &substrate_address).await.map(|link| match link {
+				Some(link) => (link.substrate_address, Some(link.evm_address), link.claimed),
+				None => (substrate_address, None, false),
+			})
+		},
+		Err(_) =>
+			return HttpResponse::BadRequest().json(ErrorResponse {
+				message: "Invalid substrate or EVM address format".to_string(),
+			}),
+	};
+
+	let (substrate_address, evm_address, claimed) = match link {
+		Ok(link) => link,
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			return HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving account link".to_string() });
+		},
+	};
+
+	let native = data
+		.surreal_db
+		.setup_account_db()
+		.await
+		.get_item_by_field("substrate_address", substrate_address.clone())
+		.await;
+	let evm = match &evm_address {
+		Some(evm_address) =>
+			data.surreal_db
+				.setup_evm_account_db()
+				.await
+				.get_item(&format!("evm_{}", evm_address))
+				.await,
+		None => Ok(None),
+	};
+
+	match (native, evm) {
+		(Ok(native), Ok(evm)) => HttpResponse::Ok().json(LinkedAccount {
+			substrate_address,
+			evm_address,
+			claimed,
+			native,
+			evm,
+		}),
+		(Err(err), _) | (_, Err(err)) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving account data".to_string() })
+		},
+	}
+}
+
+// Number of transfers and extrinsics returned with an account
+const RECENT_ACTIVITY_LIMIT: u64 = 20;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct AccountBalance {
+	/// Free balance in plancks, u128 in decimal
+	pub free: String,
+	/// Reserved balance in plancks, u128 in decimal
+	pub reserved: String,
+	/// Frozen balance in plancks, u128 in decimal
+	pub frozen: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct AccountIdentity {
+	/// Display name
+	pub display_name: Option<String>,
+	/// Legal name
+	pub legal_name: Option<String>,
+	/// Website
+	pub web: Option<String>,
+	/// Riot/matrix handle
+	pub riot: Option<String>,
+	/// Email
+	pub email: Option<String>,
+	/// Twitter handle
+	pub twitter: Option<String>,
+	/// Image
+	pub image: Option<String>,
+	/// Registrar judgement
+	pub judgement: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct AccountStaking {
+	/// Roles of the account: stash, controller, validator and nominator
+	pub roles: Vec<String>,
+	/// Stash of the bond the account is part of
+	pub stash: Option<String>,
+	/// Controller of the bond the account is part of
+	pub controller: Option<String>,
+	/// Bonded amount in plancks, u128 in decimal
+	pub bonded: String,
+	/// Amount at stake in plancks, u128 in decimal
+	pub active: String,
+	/// Validators nominated by the stash
+	pub nominations: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct AccountDetail {
+	/// Substrate address
+	pub address: String,
+	/// Current balances, none when the account does not exist on chain
+	pub balance: Option<AccountBalance>,
+	/// Number of transactions sent
+	pub nonce: u32,
+	/// On-chain identity
+	pub identity: Option<AccountIdentity>,
+	/// Staking bond and intent
+	pub staking: AccountStaking,
+	/// EVM address linked to the account, if any
+	pub evm_address: Option<String>,
+	/// Whether the EVM link is a claim rather than the hashed address mapping
+	pub evm_claimed: bool,
+	/// Latest transfers sent or received, newest first
+	pub transfers: Vec<SubstrateTransfer>,
+	/// Latest extrinsics signed, newest first
+	pub extrinsics: Vec<SubstrateExtrinsic>,
+}
+
+/// Get balances, identity, staking role and recent activity of an account
+#[utoipa::path(
+    get,
+    path = "/accounts/{address}",
+    params(
+        ("address" = String, Path, description = "Substrate address to query", example = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+    ),
+    responses(
+        (status = 200, description = "Account details", body = AccountDetail),
+        (status = 400, description = "Invalid address format", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+        (status = 503, description = "Substrate node unreachable", body = ErrorResponse),
+    ),
+    tag = "accounts"
+)]
+pub async fn get_account_detail(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+) -> impl Responder {
+	let address = match normalize_ss58_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid substrate address format".to_string() }),
+	};
+
+	let client = match data.substrate_client().await {
+		Ok(client) => client,
+		Err(err) => {
+			log::error!("Failed to connect to the substrate node: {:?}", err);
+			return HttpResponse::ServiceUnavailable()
+				.json(ErrorResponse { message: "Substrate node unreachable".to_string() });
+		},
+	};
+
+	match find_account_detail(&data, client, address).await {
+		Ok(detail) => HttpResponse::Ok().json(detail),
+		Err(err) => {
+			log::error!("Error retrieving account details: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving account details".to_string() })
+		},
+	}
+}
+
+async fn find_account_detail(
+	data: &AppState,
+	client: &SubstrateClient,
+	address: String,
+) -> Result<AccountDetail> {
+	let account = client.check_balance(&address, None).await?;
+	let identity = client.get_account_identity(&address).await?;
+	let staking = client.get_staking_role(&address).await?;
+
+	let link = find_evm_link(&data.surreal_db.setup_account_link_db().await, &address).await?;
+
+	let transfer_db = data.surreal_db.setup_transfer_db().await;
+	let mut transfers = [
+		transfer_db
+			.get_items_by_filter(
+				vec![("from_address", "=", json!(address))],
+				&["block_number", "event_index"],
+				SortOrder::Desc,
+				RECENT_ACTIVITY_LIMIT,
+			)
+			.await?,
+		transfer_db
+			.get_items_by_filter(
+				vec![("to_address", "=", json!(address))],
+				&["block_number", "event_index"],
+				SortOrder::Desc,
+				RECENT_ACTIVITY_LIMIT,
+			)
+			.await?,
+	]
+	.concat();
+	// Newest first; a transfer to oneself appears in both lists
+	transfers.sort_by(|a, b| (b.block_number, b.event_index).cmp(&(a.block_number, a.event_index)));
+	transfers.dedup_by(|a, b| (a.block_number, a.event_index) == (b.block_number, b.event_index));
+	transfers.truncate(RECENT_ACTIVITY_LIMIT as usize);
+
+	let extrinsics = data
+		.surreal_db
+		.setup_extrinsic_db()
+		.await
+		.get_items_by_filter(
+			vec![("signer", "=", json!(address))],
+			&["block_number", "extrinsic_index"],
+			SortOrder::Desc,
+			RECENT_ACTIVITY_LIMIT,
+		)
+		.await?;
+
+	let mut roles = Vec::new();
+	if staking.stash.as_ref() == Some(&address) {
+		roles.push("stash".to_string());
+	}
+	if staking.controller.as_ref() == Some(&address) {
+		roles.push("controller".to_string());
+	}
+	if staking.is_validator {
+		roles.push("validator".to_string());
+	}
+	if !staking.nominations.is_empty() {
+		roles.push("nominator".to_string());
+	}
+
+	Ok(AccountDetail {
+		nonce: account.as_ref().map_or(0, |account| account.nonce),
+		balance: account.map(|account| AccountBalance {
+			free: account.free.to_string(),
+			reserved: account.reserved.to_string(),
+			frozen: account.lock.to_string(),
+		}),
+		identity: identity.map(|identity| AccountIdentity {
+			display_name: identity.display_name,
+			legal_name: identity.legal_name,
+			web: identity.web,
+			riot: identity.riot,
+			email: identity.email,
+			twitter: identity.twitter,
+			image: identity.image,
+			judgement: identity.judgement,
+		}),
+		staking: AccountStaking {
+			roles,
+			stash: staking.stash,
+			controller: staking.controller,
+			bonded: staking.bonded.to_string(),
+			active: staking.active.to_string(),
+			nominations: staking.nominations,
+		},
+		evm_address: link.as_ref().map(|link| link.evm_address.clone()),
+		evm_claimed: link.is_some_and(|link| link.claimed),
+		address,
+		transfers,
+		extrinsics,
+	})
+}
+
+// A claimed link wins over hashed ones pointing at the same account
+async fn find_evm_link(
+	link_db: &GenericDB<AccountLink>,
+	substrate_address: &str,
+) -> Result<Option<AccountLink>> {
+	link_db
+		.get_items_by_field(
+			"substrate_address",
+			substrate_address.to_string(),
+			"claimed",
+			SortOrder::Desc,
+			1,
+		)
+		.await
+		.map(|links| links.into_iter().next())
+}
+
 /// Get paginated list of accounts
 #[utoipa::path(
     get,
@@ -0,0 +1,153 @@
+use crate::{
+	handlers::account_handler::ErrorResponse,
+	state::app_state::AppState,
+	utils::pagination::{PaginatedResponse, PaginationLinks, PaginationParams},
+};
+use selendra_db::{db::SortOrder, models::block::SubstrateBlock};
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+/// Get paginated list of blocks, newest first
+#[utoipa::path(
+    get,
+    path = "/blocks",
+    params(
+        ("page" = usize, Query, description = "Page number", example = 1),
+        ("page_size" = usize, Query, description = "Number of items per page", example = 10),
+        ("sort_order" = String, Query, description = "Sort order by block number (asc/desc)", example = "desc")
+    ),
+    responses(
+        (status = 200, description = "Blocks retrieved successfully", body = PaginatedResponse<SubstrateBlock>),
+        (status = 400, description = "Invalid pagination parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "blocks"
+)]
+pub async fn get_blocks(
+	data: web::Data<AppState>,
+	query: web::Query<PaginationParams>,
+	req: HttpRequest,
+) -> impl Responder {
+	if let Err(err) = query.validate() {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: format!("Invalid pagination parameters: {}", err) });
+	}
+
+	let db = data.surreal_db.setup_block_db().await;
+	let sort_order = match query.sort_order.to_lowercase().as_str() {
+		"asc" => SortOrder::Asc,
+		_ => SortOrder::Desc,
+	};
+
+	match db
+		.get_paginated_by_sort(query.page, query.page_size, "block_number", sort_order)
+		.await
+	{
+		Ok(response) => {
+			let base_url = format!(
+				"{}://{}{}",
+				req.connection_info().scheme(),
+				req.connection_info().host(),
+				req.path()
+			);
+
+			let links = PaginationLinks::new(
+				&base_url,
+				response.page,
+				response.page_size,
+				response.total_pages,
+				"block_number",
+				&query.sort_order,
+			);
+
+			HttpResponse::Ok().json(PaginatedResponse {
+				items: response.items,
+				total: response.total,
+				page: response.page,
+				page_size: response.page_size,
+				total_pages: response.total_pages,
+				links,
+			})
+		},
+		Err(err) => {
+			log::error!("Database error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving blocks".to_string() })
+		},
+	}
+}
+
+/// Get the latest indexed block
+#[utoipa::path(
+    get,
+    path = "/blocks/latest",
+    responses(
+        (status = 200, description = "Latest block", body = SubstrateBlock),
+        (status = 404, description = "No block indexed yet", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "blocks"
+)]
+pub async fn get_latest_block(data: web::Data<AppState>) -> impl Responder {
+	let db = data.surreal_db.setup_block_db().await;
+
+	match db.get_last_items(1, "block_number", SortOrder::Desc).await {
+		Ok(blocks) => match blocks.into_iter().next() {
+			Some(block) => HttpResponse::Ok().json(block),
+			None => HttpResponse::NotFound()
+				.json(ErrorResponse { message: "No block indexed yet".to_string() }),
+		},
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving block".to_string() })
+		},
+	}
+}
+
+/// Get a block by number or hash
+#[utoipa::path(
+    get,
+    path = "/blocks/{number_or_hash}",
+    params(
+        ("number_or_hash" = String, Path, description = "Block number or 0x prefixed block hash", example = "1000")
+    ),
+    responses(
+        (status = 200, description = "Block with runtime version and call summaries", body = SubstrateBlock),
+        (status = 400, description = "Invalid block number or hash", body = ErrorResponse),
+        (status = 404, description = "Block not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "blocks"
+)]
+pub async fn get_block(data: web::Data<AppState>, path: web::Path<String>) -> impl Responder {
+	let number_or_hash = path.into_inner();
+	let db = data.surreal_db.setup_block_db().await;
+
+	let block = if let Ok(number) = number_or_hash.parse::<u32>() {
+		db.get_item(&format!("block_{}", number)).await
+	} else if is_block_hash(&number_or_hash) {
+		db.get_item_by_field("block_hash", number_or_hash.to_lowercase()).await
+	} else {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: "Invalid block number or hash".to_string() });
+	};
+
+	match block {
+		Ok(Some(block)) => HttpResponse::Ok().json(block),
+		Ok(None) => HttpResponse::NotFound()
+			.json(ErrorResponse { message: format!("Block not found: {}", number_or_hash) }),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving block".to_string() })
+		},
+	}
+}
+
+fn is_block_hash(value: &str) -> bool {
+	value
+		.strip_prefix("0x")
+		.is_some_and(|hash| hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+}
@@ -0,0 +1,151 @@
+use crate::{handlers::account_handler::ErrorResponse, state::app_state::AppState};
+use selendra_db::models::contract::ContractAbi;
+use selendra_rust_client::{
+	address::normalize_evm_address, chain_state::evm::contracts::decoder::parse_abi_json,
+};
+
+use actix_web::{web, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterAbiRequest {
+	/// Optional contract name
+	pub name: Option<String>,
+	/// JSON ABI, either the bare array or a compiler artifact with an `abi` field
+	#[schema(value_type = Object)]
+	pub abi: Value,
+}
+
+/// Register the ABI of a contract so its calls and logs are decoded.
+///
+/// Every function in the ABI must have its selector in the deployed bytecode, and an address
+/// keeps the first ABI registered for it. A proxy is registered with its own ABI, since the
+/// functions of its implementation are not in its code.
+#[utoipa::path(
+    post,
+    path = "/contracts/{address}/abi",
+    params(
+        ("address" = String, Path, description = "Contract address", example = "0x0000000000000000000000000000000000000001")
+    ),
+    request_body = RegisterAbiRequest,
+    responses(
+        (status = 200, description = "ABI registered", body = ContractAbi),
+        (status = 400, description = "Invalid address or ABI, or the ABI does not match the deployed code", body = ErrorResponse),
+        (status = 409, description = "An ABI is already registered for the address", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+        (status = 503, description = "EVM node unreachable", body = ErrorResponse),
+    ),
+    tag = "contracts"
+)]
+pub async fn register_contract_abi(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+	request: web::Json<RegisterAbiRequest>,
+) -> impl Responder {
+	let address = match normalize_evm_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid EVM address format".to_string() }),
+	};
+
+	let abi_json = request.abi.to_string();
+	let abi = match parse_abi_json(&abi_json) {
+		Ok(abi) => abi,
+		Err(err) =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: format!("Invalid ABI: {}", err) }),
+	};
+
+	let id = format!("abi_{}", address);
+	let db = data.surreal_db.setup_contract_abi_db().await;
+
+	match db.get_item(&id).await {
+		Ok(None) => {},
+		Ok(Some(_)) =>
+			return HttpResponse::Conflict().json(ErrorResponse {
+				message: format!("An ABI is already registered for {}", address),
+			}),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			return HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error registering contract ABI".to_string() });
+		},
+	}
+
+	match data.evm_client.unmatched_abi_functions(&address, &abi).await {
+		Ok(Some(missing)) if missing.is_empty() => {},
+		Ok(Some(missing)) =>
+			return HttpResponse::BadRequest().json(ErrorResponse {
+				message: format!(
+					"Functions not found in the contract code: {}",
+					missing.join(", ")
+				),
+			}),
+		Ok(None) =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: format!("No contract deployed at {}", address) }),
+		Err(err) => {
+			log::error!("EVM node query error: {:?}", err);
+			return HttpResponse::ServiceUnavailable()
+				.json(ErrorResponse { message: "EVM node unreachable".to_string() });
+		},
+	}
+
+	let contract_abi =
+		ContractAbi { address: address.clone(), name: request.name.clone(), abi: abi_json };
+
+	// CREATE fails when a concurrent request stored the address first
+	match db.insert_item(&id, contract_abi).await {
+		Ok(Some(contract_abi)) => HttpResponse::Ok().json(contract_abi),
+		Ok(None) => HttpResponse::InternalServerError()
+			.json(ErrorResponse { message: "ABI was not stored".to_string() }),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error registering contract ABI".to_string() })
+		},
+	}
+}
+
+/// Get the registered ABI of a contract
+#[utoipa::path(
+    get,
+    path = "/contracts/{address}/abi",
+    params(
+        ("address" = String, Path, description = "Contract address", example = "0x0000000000000000000000000000000000000001")
+    ),
+    responses(
+        (status = 200, description = "Registered ABI", body = ContractAbi),
+        (status = 400, description = "Invalid address format", body = ErrorResponse),
+        (status = 404, description = "No ABI registered", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "contracts"
+)]
+pub async fn get_contract_abi(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+) -> impl Responder {
+	let address = match normalize_evm_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid EVM address format".to_string() }),
+	};
+
+	let db = data.surreal_db.setup_contract_abi_db().await;
+
+	match db.get_item(&format!("abi_{}", address)).await {
+		Ok(Some(contract_abi)) => HttpResponse::Ok().json(contract_abi),
+		Ok(None) => HttpResponse::NotFound()
+			.json(ErrorResponse { message: format!("No ABI registered for {}", address) }),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving contract ABI".to_string() })
+		},
+	}
+}
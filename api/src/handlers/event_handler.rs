@@ -0,0 +1,160 @@
+use crate::{
+	handlers::account_handler::ErrorResponse,
+	state::app_state::AppState,
+	utils::pagination::{PaginatedResponse, PaginationLinks, PaginationParams},
+};
+use selendra_db::{db::SortOrder, models::event::SubstrateEvent};
+use selendra_rust_client::address::normalize_ss58_address;
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EventFilter {
+	/// Pallet name
+	pub pallet: Option<String>,
+	/// Event name
+	pub method: Option<String>,
+	/// Signer substrate address of the emitting extrinsic
+	pub signer: Option<String>,
+	/// First block of the range, inclusive
+	pub from_block: Option<u32>,
+	/// Last block of the range, inclusive
+	pub to_block: Option<u32>,
+	/// Only events of successful or only of failed extrinsics
+	pub success: Option<bool>,
+}
+
+impl EventFilter {
+	fn conditions(&self, signer: Option<String>) -> Vec<(&'static str, &'static str, Value)> {
+		let mut conditions = Vec::new();
+		if let Some(pallet) = &self.pallet {
+			conditions.push(("pallet", "=", json!(pallet)));
+		}
+		if let Some(method) = &self.method {
+			conditions.push(("method", "=", json!(method)));
+		}
+		if let Some(signer) = signer {
+			conditions.push(("signer", "=", json!(signer)));
+		}
+		if let Some(from_block) = self.from_block {
+			conditions.push(("block_number", ">=", json!(from_block)));
+		}
+		if let Some(to_block) = self.to_block {
+			conditions.push(("block_number", "<=", json!(to_block)));
+		}
+		if let Some(success) = self.success {
+			conditions.push(("success", "=", json!(success)));
+		}
+		conditions
+	}
+
+	fn query_params(&self) -> Vec<(&'static str, String)> {
+		[
+			("pallet", self.pallet.clone()),
+			("method", self.method.clone()),
+			("signer", self.signer.clone()),
+			("from_block", self.from_block.map(|block| block.to_string())),
+			("to_block", self.to_block.map(|block| block.to_string())),
+			("success", self.success.map(|success| success.to_string())),
+		]
+		.into_iter()
+		.filter_map(|(name, value)| value.map(|value| (name, value)))
+		.collect()
+	}
+}
+
+/// Get paginated list of events, newest first
+#[utoipa::path(
+    get,
+    path = "/events",
+    params(
+        ("page" = usize, Query, description = "Page number", example = 1),
+        ("page_size" = usize, Query, description = "Number of items per page", example = 10),
+        ("sort_order" = String, Query, description = "Sort order by block number (asc/desc)", example = "desc"),
+        ("pallet" = Option<String>, Query, description = "Pallet name", example = "Balances"),
+        ("method" = Option<String>, Query, description = "Event name", example = "Transfer"),
+        ("signer" = Option<String>, Query, description = "Signer substrate address of the emitting extrinsic", example = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+        ("from_block" = Option<u32>, Query, description = "First block of the range, inclusive", example = 1000),
+        ("to_block" = Option<u32>, Query, description = "Last block of the range, inclusive", example = 2000),
+        ("success" = Option<bool>, Query, description = "Only events of successful or only of failed extrinsics", example = true)
+    ),
+    responses(
+        (status = 200, description = "Events retrieved successfully", body = PaginatedResponse<SubstrateEvent>),
+        (status = 400, description = "Invalid parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "events"
+)]
+pub async fn get_events(
+	data: web::Data<AppState>,
+	query: web::Query<PaginationParams>,
+	filter: web::Query<EventFilter>,
+	req: HttpRequest,
+) -> impl Responder {
+	if let Err(err) = query.validate() {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: format!("Invalid pagination parameters: {}", err) });
+	}
+
+	let signer = match filter.signer.as_deref().map(normalize_ss58_address) {
+		Some(None) =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid substrate address format".to_string() }),
+		Some(signer) => signer,
+		None => None,
+	};
+
+	let db = data.surreal_db.setup_event_db().await;
+	let sort_order = match query.sort_order.to_lowercase().as_str() {
+		"asc" => SortOrder::Asc,
+		_ => SortOrder::Desc,
+	};
+
+	match db
+		.get_paginated_by_filter(
+			query.page,
+			query.page_size,
+			filter.conditions(signer),
+			&["block_number", "event_index"],
+			sort_order,
+		)
+		.await
+	{
+		Ok(response) => {
+			let base_url = format!(
+				"{}://{}{}",
+				req.connection_info().scheme(),
+				req.connection_info().host(),
+				req.path()
+			);
+
+			let links = PaginationLinks::with_filters(
+				&base_url,
+				response.page,
+				response.page_size,
+				response.total_pages,
+				"block_number",
+				&query.sort_order,
+				&filter.query_params(),
+			);
+
+			HttpResponse::Ok().json(PaginatedResponse {
+				items: response.items,
+				total: response.total,
+				page: response.page,
+				page_size: response.page_size,
+				total_pages: response.total_pages,
+				links,
+			})
+		},
+		Err(err) => {
+			log::error!("Database error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving events".to_string() })
+		},
+	}
+}
@@ -0,0 +1,334 @@
+use crate::{
+	handlers::account_handler::ErrorResponse,
+	state::app_state::AppState,
+	utils::pagination::{PaginatedResponse, PaginationLinks, PaginationParams},
+};
+use selendra_db::{
+	db::SortOrder,
+	models::{
+		account::EvmAccount,
+		contract::Contract,
+		evm::{EvmBlockHeader, EvmLog, EvmTransaction, InternalTransaction},
+		token::{Token, TokenTransfer},
+	},
+};
+use selendra_rust_client::address::normalize_evm_address;
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+// Upper bound on the logs, transfers and internal calls returned with one transaction
+const MAX_TRANSACTION_ITEMS: u64 = 1000;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct EvmActivityQuery {
+	/// Maximum number of transactions and transfers
+	#[validate(range(min = 1, max = 100))]
+	#[serde(default = "default_activity_limit")]
+	pub limit: u64,
+}
+
+fn default_activity_limit() -> u64 {
+	25
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EvmTransactionDetail {
+	#[serde(flatten)]
+	pub transaction: EvmTransaction,
+	/// Logs emitted by the transaction
+	pub logs: Vec<EvmLog>,
+	/// Token transfers decoded from the logs
+	pub token_transfers: Vec<TokenTransfer>,
+	/// Internal calls, when tracing is enabled in the archive
+	pub internal_transactions: Vec<InternalTransaction>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EvmAddressDetail {
+	/// Lowercase address
+	pub address: String,
+	/// Balance, nonce and activity, once the address has been seen in a transaction
+	pub account: Option<EvmAccount>,
+	/// Deployment details of a contract address
+	pub contract: Option<Contract>,
+	/// Registry entry of a token contract address
+	pub token: Option<Token>,
+	/// Latest transactions sent or received, newest first
+	pub transactions: Vec<EvmTransaction>,
+	/// Latest token transfers sent or received, newest first
+	pub token_transfers: Vec<TokenTransfer>,
+}
+
+/// Get paginated list of EVM blocks, newest first
+#[utoipa::path(
+    get,
+    path = "/evm/blocks",
+    params(
+        ("page" = usize, Query, description = "Page number", example = 1),
+        ("page_size" = usize, Query, description = "Number of items per page", example = 10),
+        ("sort_order" = String, Query, description = "Sort order by block number (asc/desc)", example = "desc")
+    ),
+    responses(
+        (status = 200, description = "EVM blocks retrieved successfully", body = PaginatedResponse<EvmBlockHeader>),
+        (status = 400, description = "Invalid pagination parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "evm"
+)]
+pub async fn get_evm_blocks(
+	data: web::Data<AppState>,
+	query: web::Query<PaginationParams>,
+	req: HttpRequest,
+) -> impl Responder {
+	if let Err(err) = query.validate() {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: format!("Invalid pagination parameters: {}", err) });
+	}
+
+	let db = data.surreal_db.setup_evm_block_db().await;
+	let sort_order = match query.sort_order.to_lowercase().as_str() {
+		"asc" => SortOrder::Asc,
+		_ => SortOrder::Desc,
+	};
+
+	match db
+		.get_paginated_by_sort(query.page, query.page_size, "block_number", sort_order)
+		.await
+	{
+		Ok(response) => {
+			let base_url = format!(
+				"{}://{}{}",
+				req.connection_info().scheme(),
+				req.connection_info().host(),
+				req.path()
+			);
+
+			let links = PaginationLinks::new(
+				&base_url,
+				response.page,
+				response.page_size,
+				response.total_pages,
+				"block_number",
+				&query.sort_order,
+			);
+
+			HttpResponse::Ok().json(PaginatedResponse {
+				items: response.items,
+				total: response.total,
+				page: response.page,
+				page_size: response.page_size,
+				total_pages: response.total_pages,
+				links,
+			})
+		},
+		Err(err) => {
+			log::error!("Database error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving EVM blocks".to_string() })
+		},
+	}
+}
+
+/// Get an EVM transaction with its logs, token transfers and internal calls
+#[utoipa::path(
+    get,
+    path = "/evm/tx/{hash}",
+    params(
+        ("hash" = String, Path, description = "0x prefixed transaction hash")
+    ),
+    responses(
+        (status = 200, description = "Transaction found", body = EvmTransactionDetail),
+        (status = 400, description = "Invalid transaction hash", body = ErrorResponse),
+        (status = 404, description = "Transaction not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "evm"
+)]
+pub async fn get_evm_transaction(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+) -> impl Responder {
+	let hash = path.into_inner().to_lowercase();
+	let is_hash = hash
+		.strip_prefix("0x")
+		.is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+	if !is_hash {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: "Invalid transaction hash".to_string() });
+	}
+
+	match find_transaction(&data, &hash).await {
+		Ok(Some(transaction)) => HttpResponse::Ok().json(transaction),
+		Ok(None) => HttpResponse::NotFound()
+			.json(ErrorResponse { message: format!("Transaction not found: {}", hash) }),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving transaction".to_string() })
+		},
+	}
+}
+
+/// Get balance, latest transactions, token transfers and contract details of an EVM address
+#[utoipa::path(
+    get,
+    path = "/evm/address/{address}",
+    params(
+        ("address" = String, Path, description = "EVM address", example = "0x0000000000000000000000000000000000000001"),
+        ("limit" = u64, Query, description = "Maximum number of transactions and transfers", example = 25)
+    ),
+    responses(
+        (status = 200, description = "Address details", body = EvmAddressDetail),
+        (status = 400, description = "Invalid parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "evm"
+)]
+pub async fn get_evm_address(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+	query: web::Query<EvmActivityQuery>,
+) -> impl Responder {
+	if let Err(err) = query.validate() {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: format!("Invalid parameters: {}", err) });
+	}
+	let address = match normalize_evm_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid EVM address format".to_string() }),
+	};
+
+	match find_address(&data, address, query.limit).await {
+		Ok(detail) => HttpResponse::Ok().json(detail),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving address".to_string() })
+		},
+	}
+}
+
+async fn find_transaction(data: &AppState, hash: &str) -> Result<Option<EvmTransactionDetail>> {
+	let db = &data.surreal_db;
+
+	let transaction =
+		match db.setup_evm_transaction_db().await.get_item(&format!("tx_{}", hash)).await? {
+			Some(transaction) => transaction,
+			None => return Ok(None),
+		};
+
+	let logs = db
+		.setup_evm_log_db()
+		.await
+		.get_items_by_field(
+			"transaction_hash",
+			hash.to_string(),
+			"log_index",
+			SortOrder::Asc,
+			MAX_TRANSACTION_ITEMS,
+		)
+		.await?;
+	let token_transfers = db
+		.setup_token_transfer_db()
+		.await
+		.get_items_by_field(
+			"transaction_hash",
+			hash.to_string(),
+			"log_index",
+			SortOrder::Asc,
+			MAX_TRANSACTION_ITEMS,
+		)
+		.await?;
+	let internal_transactions = db
+		.setup_internal_transaction_db()
+		.await
+		.get_items_by_field(
+			"transaction_hash",
+			hash.to_string(),
+			"trace_index",
+			SortOrder::Asc,
+			MAX_TRANSACTION_ITEMS,
+		)
+		.await?;
+
+	Ok(Some(EvmTransactionDetail { transaction, logs, token_transfers, internal_transactions }))
+}
+
+async fn find_address(data: &AppState, address: String, limit: u64) -> Result<EvmAddressDetail> {
+	let db = &data.surreal_db;
+
+	let account = db.setup_evm_account_db().await.get_item(&format!("evm_{}", address)).await?;
+	let contract = db.setup_contract_db().await.get_item(&format!("contract_{}", address)).await?;
+	let token = db.setup_token_db().await.get_item(&format!("token_{}", address)).await?;
+
+	let evm_transaction = db.setup_evm_transaction_db().await;
+	let mut transactions = [
+		evm_transaction
+			.get_items_by_field(
+				"from_address",
+				address.clone(),
+				"block_number",
+				SortOrder::Desc,
+				limit,
+			)
+			.await?,
+		evm_transaction
+			.get_items_by_field(
+				"to_address",
+				address.clone(),
+				"block_number",
+				SortOrder::Desc,
+				limit,
+			)
+			.await?,
+	]
+	.concat();
+	// Newest first; a transaction to oneself appears in both lists
+	transactions.sort_by(|a, b| {
+		(b.block_number, b.transaction_index).cmp(&(a.block_number, a.transaction_index))
+	});
+	transactions.dedup_by(|a, b| a.transaction_hash == b.transaction_hash);
+	transactions.truncate(limit as usize);
+
+	let token_transfer = db.setup_token_transfer_db().await;
+	let mut token_transfers = [
+		token_transfer
+			.get_items_by_field(
+				"from_address",
+				address.clone(),
+				"block_number",
+				SortOrder::Desc,
+				limit,
+			)
+			.await?,
+		token_transfer
+			.get_items_by_field(
+				"to_address",
+				address.clone(),
+				"block_number",
+				SortOrder::Desc,
+				limit,
+			)
+			.await?,
+	]
+	.concat();
+	token_transfers.sort_by(|a, b| {
+		(b.block_number, b.log_index, b.batch_index).cmp(&(
+			a.block_number,
+			a.log_index,
+			a.batch_index,
+		))
+	});
+	token_transfers.dedup_by(|a, b| {
+		(a.block_number, a.log_index, a.batch_index) == (b.block_number, b.log_index, b.batch_index)
+	});
+	token_transfers.truncate(limit as usize);
+
+	Ok(EvmAddressDetail { address, account, contract, token, transactions, token_transfers })
+}
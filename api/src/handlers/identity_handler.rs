@@ -0,0 +1,103 @@
+use crate::{handlers::account_handler::ErrorResponse, state::app_state::AppState};
+use selendra_db::{
+	db::SortOrder,
+	models::identity::{Identity, IdentityHistory},
+};
+use selendra_rust_client::address::normalize_ss58_address;
+
+use actix_web::{web, HttpResponse, Responder};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct IdentitySearchQuery {
+	/// Start of a word of the display name to search for
+	#[validate(length(min = 1, max = 64))]
+	pub name: String,
+
+	/// Maximum number of results
+	#[validate(range(min = 1, max = 100))]
+	#[serde(default = "default_search_limit")]
+	pub limit: u64,
+}
+
+fn default_search_limit() -> u64 {
+	20
+}
+
+/// Search identities by display name
+#[utoipa::path(
+    get,
+    path = "/identities/search",
+    params(
+        ("name" = String, Query, description = "Display name, or the start of any of its words, to search for", example = "selendra"),
+        ("limit" = u64, Query, description = "Maximum number of results", example = 20)
+    ),
+    responses(
+        (status = 200, description = "Matching identities", body = Vec<Identity>),
+        (status = 400, description = "Invalid search parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "identities"
+)]
+pub async fn search_identities(
+	data: web::Data<AppState>,
+	query: web::Query<IdentitySearchQuery>,
+) -> impl Responder {
+	if let Err(err) = query.validate() {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: format!("Invalid search parameters: {}", err) });
+	}
+
+	let db = data.surreal_db.setup_identity_db().await;
+
+	match db.search_by_field("display_name", query.name.trim(), query.limit).await {
+		Ok(identities) => HttpResponse::Ok().json(identities),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error searching identities".to_string() })
+		},
+	}
+}
+
+/// Get identity change history of a substrate address
+#[utoipa::path(
+    get,
+    path = "/identities/{address}/history",
+    params(
+        ("address" = String, Path, description = "Substrate address to query", example = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+    ),
+    responses(
+        (status = 200, description = "Identity history, newest first", body = Vec<IdentityHistory>),
+        (status = 400, description = "Invalid address format", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "identities"
+)]
+pub async fn get_identity_history(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+) -> impl Responder {
+	let address = match normalize_ss58_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid substrate address format".to_string() }),
+	};
+
+	let db = data.surreal_db.setup_identity_history_db().await;
+
+	match db
+		.get_items_by_field("substrate_address", address, "block_number", SortOrder::Desc, 100)
+		.await
+	{
+		Ok(history) => HttpResponse::Ok().json(history),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving identity history".to_string() })
+		},
+	}
+}
@@ -1 +1,14 @@
 pub mod account_handler;
+pub mod block_handler;
+pub mod contract_handler;
+pub mod event_handler;
+pub mod evm_handler;
+pub mod extrinsic_handler;
+pub mod identity_handler;
+pub mod nft_handler;
+pub mod offence_handler;
+pub mod producer_handler;
+pub mod search_handler;
+pub mod session_handler;
+pub mod stats_handler;
+pub mod token_handler;
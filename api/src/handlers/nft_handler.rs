@@ -0,0 +1,90 @@
+use crate::{handlers::account_handler::ErrorResponse, state::app_state::AppState};
+use selendra_db::{
+	db::SortOrder,
+	models::nft::{NftBalance, NftToken},
+};
+use selendra_rust_client::address::normalize_evm_address;
+use serde_json::json;
+
+use actix_web::{web, HttpResponse, Responder};
+
+/// Get the ERC-721 tokens currently held by an EVM address
+#[utoipa::path(
+    get,
+    path = "/accounts/{address}/nfts",
+    params(
+        ("address" = String, Path, description = "EVM address of the holder", example = "0x0000000000000000000000000000000000000001")
+    ),
+    responses(
+        (status = 200, description = "Tokens held with their metadata", body = Vec<NftToken>),
+        (status = 400, description = "Invalid address format", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "nfts"
+)]
+pub async fn get_nfts_by_owner(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+) -> impl Responder {
+	// Addresses are indexed in lowercase hex
+	let address = match normalize_evm_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid EVM address format".to_string() }),
+	};
+	let db = data.surreal_db.setup_nft_token_db().await;
+
+	match db
+		.get_items_by_field("owner", address, "last_transfer_block", SortOrder::Desc, 1000)
+		.await
+	{
+		Ok(tokens) => HttpResponse::Ok().json(tokens),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving NFT holdings".to_string() })
+		},
+	}
+}
+
+/// Get the ERC-1155 tokens currently held by an EVM address
+#[utoipa::path(
+    get,
+    path = "/accounts/{address}/multi-tokens",
+    params(
+        ("address" = String, Path, description = "EVM address of the holder", example = "0x0000000000000000000000000000000000000001")
+    ),
+    responses(
+        (status = 200, description = "Token ids held with their balance and metadata", body = Vec<NftBalance>),
+        (status = 400, description = "Invalid address format", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "nfts"
+)]
+pub async fn get_multi_tokens_by_owner(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+) -> impl Responder {
+	// Addresses are indexed in lowercase hex
+	let address = match normalize_evm_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid EVM address format".to_string() }),
+	};
+	let db = data.surreal_db.setup_nft_balance_db().await;
+	let filters = vec![("owner", "=", json!(address)), ("balance", "!=", json!("0"))];
+
+	match db
+		.get_paginated_by_filter(1, 1000, filters, &["last_transfer_block"], SortOrder::Desc)
+		.await
+	{
+		Ok(result) => HttpResponse::Ok().json(result.items),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving NFT holdings".to_string() })
+		},
+	}
+}
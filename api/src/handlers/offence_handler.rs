@@ -0,0 +1,74 @@
+use crate::{handlers::account_handler::ErrorResponse, state::app_state::AppState};
+use selendra_db::{db::SortOrder, models::offence::Offence};
+use selendra_rust_client::address::normalize_ss58_address;
+
+use actix_web::{web, HttpResponse, Responder};
+
+/// Get slashes, chills and bans of a validator, newest first
+#[utoipa::path(
+    get,
+    path = "/validators/{address}/slashes",
+    params(
+        ("address" = String, Path, description = "Validator substrate address", example = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+    ),
+    responses(
+        (status = 200, description = "Offences of the validator", body = Vec<Offence>),
+        (status = 400, description = "Invalid address format", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "slashes"
+)]
+pub async fn get_validator_slashes(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+) -> impl Responder {
+	let address = match normalize_ss58_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid substrate address format".to_string() }),
+	};
+
+	let db = data.surreal_db.setup_offence_db().await;
+
+	match db
+		.get_items_by_field("offender", address, "block_number", SortOrder::Desc, 100)
+		.await
+	{
+		Ok(offences) => HttpResponse::Ok().json(offences),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving validator slashes".to_string() })
+		},
+	}
+}
+
+/// Get slashes, chills and bans in an era
+#[utoipa::path(
+    get,
+    path = "/eras/{era}/slashes",
+    params(
+        ("era" = u32, Path, description = "Era index to query", example = 300)
+    ),
+    responses(
+        (status = 200, description = "Offences in the era", body = Vec<Offence>),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "slashes"
+)]
+pub async fn get_era_slashes(data: web::Data<AppState>, path: web::Path<u32>) -> impl Responder {
+	let db = data.surreal_db.setup_offence_db().await;
+
+	match db
+		.get_items_by_field("era", path.into_inner(), "block_number", SortOrder::Desc, 1000)
+		.await
+	{
+		Ok(offences) => HttpResponse::Ok().json(offences),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving era slashes".to_string() })
+		},
+	}
+}
@@ -0,0 +1,72 @@
+use crate::{handlers::account_handler::ErrorResponse, state::app_state::AppState};
+use selendra_db::{db::SortOrder, models::session::EraValidator};
+use selendra_rust_client::address::normalize_ss58_address;
+
+use actix_web::{web, HttpResponse, Responder};
+
+/// Get blocks produced by every validator in an era
+#[utoipa::path(
+    get,
+    path = "/eras/{era}/producers",
+    params(
+        ("era" = u32, Path, description = "Era index to query", example = 300)
+    ),
+    responses(
+        (status = 200, description = "Validators with blocks produced in the era", body = Vec<EraValidator>),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "producers"
+)]
+pub async fn get_era_producers(data: web::Data<AppState>, path: web::Path<u32>) -> impl Responder {
+	let db = data.surreal_db.setup_era_validator_db().await;
+
+	match db
+		.get_items_by_field("era", path.into_inner(), "blocks_produced", SortOrder::Desc, 1000)
+		.await
+	{
+		Ok(producers) => HttpResponse::Ok().json(producers),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving era producers".to_string() })
+		},
+	}
+}
+
+/// Get blocks produced per era by a validator, newest era first
+#[utoipa::path(
+    get,
+    path = "/validators/{address}/production",
+    params(
+        ("address" = String, Path, description = "Validator substrate address", example = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+    ),
+    responses(
+        (status = 200, description = "Blocks produced per era", body = Vec<EraValidator>),
+        (status = 400, description = "Invalid address format", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "producers"
+)]
+pub async fn get_validator_production(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+) -> impl Responder {
+	let address = match normalize_ss58_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid substrate address format".to_string() }),
+	};
+
+	let db = data.surreal_db.setup_era_validator_db().await;
+
+	match db.get_items_by_field("validator", address, "era", SortOrder::Desc, 100).await {
+		Ok(production) => HttpResponse::Ok().json(production),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError().json(ErrorResponse {
+				message: "Error retrieving validator production".to_string(),
+			})
+		},
+	}
+}
@@ -0,0 +1,199 @@
+use crate::{handlers::account_handler::ErrorResponse, state::app_state::AppState};
+use selendra_db::models::{
+	account::{EvmAccount, SubstrateAccount},
+	block::SubstrateBlock,
+	evm::EvmTransaction,
+	extrinsic::SubstrateExtrinsic,
+	identity::Identity,
+	token::Token,
+};
+use selendra_rust_client::address::ChainAddress;
+
+use actix_web::{web, HttpResponse, Responder};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SearchQuery {
+	/// Block number, block or transaction hash, address, token symbol or identity name
+	#[validate(length(min = 1, max = 128))]
+	pub q: String,
+
+	/// Maximum number of results per kind
+	#[validate(range(min = 1, max = 50))]
+	#[serde(default = "default_search_limit")]
+	pub limit: u64,
+}
+
+fn default_search_limit() -> u64 {
+	10
+}
+
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchQueryType {
+	BlockNumber,
+	Hash,
+	SubstrateAddress,
+	EvmAddress,
+	Text,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct SearchResults {
+	/// Blocks by number or hash
+	pub blocks: Vec<SubstrateBlock>,
+	/// Extrinsics by hash
+	pub extrinsics: Vec<SubstrateExtrinsic>,
+	/// EVM transactions by hash
+	pub evm_transactions: Vec<EvmTransaction>,
+	/// Substrate accounts by address
+	pub accounts: Vec<SubstrateAccount>,
+	/// EVM accounts by address
+	pub evm_accounts: Vec<EvmAccount>,
+	/// Tokens by contract address or symbol
+	pub tokens: Vec<Token>,
+	/// Identities by address or display name
+	pub identities: Vec<Identity>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SearchResponse {
+	/// What the query was recognised as
+	pub query_type: SearchQueryType,
+	/// Matches grouped by kind
+	pub results: SearchResults,
+}
+
+enum SearchTerm {
+	BlockNumber(u32),
+	// A 32-byte hash is also a valid hex account id, so both are looked up
+	Hash(String, ChainAddress),
+	Address(ChainAddress),
+	Text(String),
+}
+
+impl SearchTerm {
+	fn classify(query: &str) -> Self {
+		let query = query.trim();
+		if let Ok(number) = query.parse::<u32>() {
+			return Self::BlockNumber(number);
+		}
+		match ChainAddress::parse(query) {
+			Ok(address) if query.len() == 66 => Self::Hash(query.to_lowercase(), address),
+			Ok(address) => Self::Address(address),
+			Err(_) => Self::Text(query.to_string()),
+		}
+	}
+
+	fn query_type(&self) -> SearchQueryType {
+		match self {
+			Self::BlockNumber(_) => SearchQueryType::BlockNumber,
+			Self::Hash(..) => SearchQueryType::Hash,
+			Self::Address(address) if address.is_evm() => SearchQueryType::EvmAddress,
+			Self::Address(_) => SearchQueryType::SubstrateAddress,
+			Self::Text(_) => SearchQueryType::Text,
+		}
+	}
+}
+
+/// Search blocks, transactions, accounts, tokens and identities
+#[utoipa::path(
+    get,
+    path = "/search",
+    params(
+        ("q" = String, Query, description = "Block number, block or transaction hash, SS58 or EVM address, token symbol or identity name", example = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+        ("limit" = u64, Query, description = "Maximum number of results per kind", example = 10)
+    ),
+    responses(
+        (status = 200, description = "Matches grouped by kind", body = SearchResponse),
+        (status = 400, description = "Invalid search parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "search"
+)]
+pub async fn search(data: web::Data<AppState>, query: web::Query<SearchQuery>) -> impl Responder {
+	if let Err(err) = query.validate() {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: format!("Invalid search parameters: {}", err) });
+	}
+
+	let term = SearchTerm::classify(&query.q);
+
+	match find_matches(&data, &term, query.limit).await {
+		Ok(results) =>
+			HttpResponse::Ok().json(SearchResponse { query_type: term.query_type(), results }),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error searching".to_string() })
+		},
+	}
+}
+
+async fn find_matches(data: &AppState, term: &SearchTerm, limit: u64) -> Result<SearchResults> {
+	let db = &data.surreal_db;
+	let mut results = SearchResults::default();
+
+	match term {
+		SearchTerm::BlockNumber(number) => {
+			results
+				.blocks
+				.extend(db.setup_block_db().await.get_item(&format!("block_{}", number)).await?);
+		},
+		SearchTerm::Hash(hash, account) => {
+			results.blocks.extend(
+				db.setup_block_db().await.get_item_by_field("block_hash", hash.clone()).await?,
+			);
+			results.extrinsics.extend(
+				db.setup_extrinsic_db().await.get_item_by_field("hash", hash.clone()).await?,
+			);
+			results.evm_transactions.extend(
+				db.setup_evm_transaction_db().await.get_item(&format!("tx_{}", hash)).await?,
+			);
+			results.accounts.extend(
+				db.setup_account_db()
+					.await
+					.get_item_by_field("substrate_address", account.canonical())
+					.await?,
+			);
+		},
+		SearchTerm::Address(address @ ChainAddress::Evm(_)) => {
+			let address = address.canonical();
+			results.evm_accounts.extend(
+				db.setup_evm_account_db().await.get_item(&format!("evm_{}", address)).await?,
+			);
+			results
+				.tokens
+				.extend(db.setup_token_db().await.get_item(&format!("token_{}", address)).await?);
+		},
+		SearchTerm::Address(address) => {
+			let address = address.canonical();
+			results.accounts.extend(
+				db.setup_account_db()
+					.await
+					.get_item_by_field("substrate_address", address.clone())
+					.await?,
+			);
+			results.identities.extend(
+				db.setup_identity_db()
+					.await
+					.get_item_by_field("substrate_address", address)
+					.await?,
+			);
+		},
+		SearchTerm::Text(text) => {
+			results.tokens =
+				db.setup_token_db().await.search_by_field("symbol", text, limit).await?;
+			results.identities = db
+				.setup_identity_db()
+				.await
+				.search_by_field("display_name", text, limit)
+				.await?;
+		},
+	}
+
+	Ok(results)
+}
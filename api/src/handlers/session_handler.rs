@@ -0,0 +1,120 @@
+use crate::{
+	handlers::account_handler::ErrorResponse,
+	state::app_state::AppState,
+	utils::pagination::{PaginatedResponse, PaginationLinks, PaginationParams},
+};
+use selendra_db::{
+	db::SortOrder,
+	models::session::{Session, SessionValidator},
+};
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+/// Get paginated list of sessions, newest first by default
+#[utoipa::path(
+    get,
+    path = "/sessions",
+    params(
+        ("page" = usize, Query, description = "Page number", example = 1),
+        ("page_size" = usize, Query, description = "Number of items per page", example = 10),
+        ("sort_order" = String, Query, description = "Sort order (asc/desc)", example = "desc")
+    ),
+    responses(
+        (status = 200, description = "Sessions retrieved successfully", body = PaginatedResponse<Session>),
+        (status = 400, description = "Invalid pagination parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "sessions"
+)]
+pub async fn get_sessions(
+	data: web::Data<AppState>,
+	query: web::Query<PaginationParams>,
+	req: HttpRequest,
+) -> impl Responder {
+	if let Err(err) = query.validate() {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: format!("Invalid pagination parameters: {}", err) });
+	}
+
+	let db = data.surreal_db.setup_session_db().await;
+	let sort_order = match query.sort_order.to_lowercase().as_str() {
+		"asc" => SortOrder::Asc,
+		_ => SortOrder::Desc,
+	};
+
+	match db
+		.get_paginated_by_sort(query.page, query.page_size, "session_index", sort_order)
+		.await
+	{
+		Ok(response) => {
+			let base_url = format!(
+				"{}://{}{}",
+				req.connection_info().scheme(),
+				req.connection_info().host(),
+				req.path()
+			);
+
+			let links = PaginationLinks::new(
+				&base_url,
+				response.page,
+				response.page_size,
+				response.total_pages,
+				"session_index",
+				&query.sort_order,
+			);
+
+			HttpResponse::Ok().json(PaginatedResponse {
+				items: response.items,
+				total: response.total,
+				page: response.page,
+				page_size: response.page_size,
+				total_pages: response.total_pages,
+				links,
+			})
+		},
+		Err(err) => {
+			log::error!("Database error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving sessions".to_string() })
+		},
+	}
+}
+
+/// Get block production of every validator in a session
+#[utoipa::path(
+    get,
+    path = "/sessions/{session_index}/validators",
+    params(
+        ("session_index" = u32, Path, description = "Session index to query", example = 1200)
+    ),
+    responses(
+        (status = 200, description = "Validators with blocks produced in the session", body = Vec<SessionValidator>),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "sessions"
+)]
+pub async fn get_session_validators(
+	data: web::Data<AppState>,
+	path: web::Path<u32>,
+) -> impl Responder {
+	let db = data.surreal_db.setup_session_validator_db().await;
+
+	match db
+		.get_items_by_field(
+			"session_index",
+			path.into_inner(),
+			"blocks_produced",
+			SortOrder::Desc,
+			1000,
+		)
+		.await
+	{
+		Ok(validators) => HttpResponse::Ok().json(validators),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving session validators".to_string() })
+		},
+	}
+}
@@ -0,0 +1,54 @@
+use crate::{handlers::account_handler::ErrorResponse, state::app_state::AppState};
+use selendra_db::{db::SortOrder, models::stats::DailyStats};
+
+use actix_web::{web, HttpResponse, Responder};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct DailyStatsQuery {
+	/// Number of most recent days to return
+	#[validate(range(min = 1, max = 365))]
+	#[serde(default = "default_days")]
+	pub days: u64,
+}
+
+fn default_days() -> u64 {
+	30
+}
+
+/// Get chain-wide daily aggregates, newest day first
+#[utoipa::path(
+    get,
+    path = "/stats/daily",
+    params(
+        ("days" = u64, Query, description = "Number of most recent days", example = 30)
+    ),
+    responses(
+        (status = 200, description = "Daily statistics", body = Vec<DailyStats>),
+        (status = 400, description = "Invalid parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "stats"
+)]
+pub async fn get_daily_stats(
+	data: web::Data<AppState>,
+	query: web::Query<DailyStatsQuery>,
+) -> impl Responder {
+	if let Err(err) = query.validate() {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: format!("Invalid parameters: {}", err) });
+	}
+
+	let db = data.surreal_db.setup_daily_stats_db().await;
+
+	match db.get_last_items(query.days, "date", SortOrder::Desc).await {
+		Ok(stats) => HttpResponse::Ok().json(stats),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving daily statistics".to_string() })
+		},
+	}
+}
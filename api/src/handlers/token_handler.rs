@@ -0,0 +1,236 @@
+use crate::{handlers::account_handler::ErrorResponse, state::app_state::AppState};
+use selendra_db::{
+	db::SortOrder,
+	models::token::{Token, TokenHolder, TokenTransfer},
+};
+use selendra_rust_client::{address::normalize_evm_address, utils::format_token_amount};
+
+use actix_web::{web, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct TokenQuery {
+	/// Maximum number of transfers
+	#[validate(range(min = 1, max = 100))]
+	#[serde(default = "default_transfers_limit")]
+	pub limit: u64,
+}
+
+fn default_transfers_limit() -> u64 {
+	25
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct TokenHoldersQuery {
+	/// Maximum number of holders
+	#[validate(range(min = 1, max = 100))]
+	#[serde(default = "default_holders_limit")]
+	pub limit: u64,
+}
+
+fn default_holders_limit() -> u64 {
+	20
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenDetail {
+	#[serde(flatten)]
+	pub token: Token,
+	/// Latest transfers of the token, newest first
+	pub transfers: Vec<TokenTransfer>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenBalance {
+	/// Token registry entry
+	pub token: Token,
+	/// Raw balance as a decimal string
+	pub balance: String,
+	/// Balance scaled by the token decimals
+	pub formatted_balance: String,
+}
+
+/// Get a token from the registry with its latest transfers
+#[utoipa::path(
+    get,
+    path = "/tokens/{address}",
+    params(
+        ("address" = String, Path, description = "Token contract address", example = "0x0000000000000000000000000000000000000001"),
+        ("limit" = u64, Query, description = "Maximum number of transfers", example = 25)
+    ),
+    responses(
+        (status = 200, description = "Token found", body = TokenDetail),
+        (status = 400, description = "Invalid parameters", body = ErrorResponse),
+        (status = 404, description = "Token not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "tokens"
+)]
+pub async fn get_token(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+	query: web::Query<TokenQuery>,
+) -> impl Responder {
+	if let Err(err) = query.validate() {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: format!("Invalid parameters: {}", err) });
+	}
+	let address = match normalize_evm_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid EVM address format".to_string() }),
+	};
+
+	let token = match data
+		.surreal_db
+		.setup_token_db()
+		.await
+		.get_item(&format!("token_{}", address))
+		.await
+	{
+		Ok(Some(token)) => token,
+		Ok(None) =>
+			return HttpResponse::NotFound()
+				.json(ErrorResponse { message: format!("Token not found: {}", address) }),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			return HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving token".to_string() });
+		},
+	};
+
+	let transfers = data
+		.surreal_db
+		.setup_token_transfer_db()
+		.await
+		.get_items_by_field("token_address", address, "block_number", SortOrder::Desc, query.limit)
+		.await;
+
+	match transfers {
+		Ok(transfers) => HttpResponse::Ok().json(TokenDetail { token, transfers }),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving token transfers".to_string() })
+		},
+	}
+}
+
+/// Get the largest holders of a token
+#[utoipa::path(
+    get,
+    path = "/tokens/{address}/holders",
+    params(
+        ("address" = String, Path, description = "Token contract address", example = "0x0000000000000000000000000000000000000001"),
+        ("limit" = u64, Query, description = "Maximum number of holders", example = 20)
+    ),
+    responses(
+        (status = 200, description = "Holders by balance, largest first", body = Vec<TokenHolder>),
+        (status = 400, description = "Invalid parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "tokens"
+)]
+pub async fn get_token_holders(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+	query: web::Query<TokenHoldersQuery>,
+) -> impl Responder {
+	if let Err(err) = query.validate() {
+		return HttpResponse::BadRequest()
+			.json(ErrorResponse { message: format!("Invalid parameters: {}", err) });
+	}
+	let address = match normalize_evm_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid EVM address format".to_string() }),
+	};
+
+	let db = data.surreal_db.setup_token_holder_db().await;
+
+	// Balances are decimal strings, so they are ordered numerically rather than lexically
+	match db
+		.get_items_by_field(
+			"token_address",
+			address,
+			"balance NUMERIC",
+			SortOrder::Desc,
+			query.limit,
+		)
+		.await
+	{
+		Ok(holders) => HttpResponse::Ok()
+			.json(holders.into_iter().filter(|holder| holder.balance != "0").collect::<Vec<_>>()),
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving token holders".to_string() })
+		},
+	}
+}
+
+/// Get the ERC-20 tokens held by an address
+#[utoipa::path(
+    get,
+    path = "/accounts/{address}/tokens",
+    params(
+        ("address" = String, Path, description = "EVM address of the wallet", example = "0x0000000000000000000000000000000000000001")
+    ),
+    responses(
+        (status = 200, description = "Non-zero token balances", body = Vec<TokenBalance>),
+        (status = 400, description = "Invalid address format", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "tokens"
+)]
+pub async fn get_token_portfolio(
+	data: web::Data<AppState>,
+	path: web::Path<String>,
+) -> impl Responder {
+	let address = match normalize_evm_address(&path) {
+		Some(address) => address,
+		None =>
+			return HttpResponse::BadRequest()
+				.json(ErrorResponse { message: "Invalid EVM address format".to_string() }),
+	};
+
+	let holder_db = data.surreal_db.setup_token_holder_db().await;
+	let token_db = data.surreal_db.setup_token_db().await;
+
+	let holdings = match holder_db
+		.get_items_by_field("holder", address, "last_updated_block", SortOrder::Desc, 1000)
+		.await
+	{
+		Ok(holdings) => holdings,
+		Err(err) => {
+			log::error!("Database query error: {:?}", err);
+			return HttpResponse::InternalServerError()
+				.json(ErrorResponse { message: "Error retrieving token portfolio".to_string() });
+		},
+	};
+
+	let mut balances = Vec::new();
+	for holding in holdings.into_iter().filter(|holding| holding.balance != "0") {
+		match token_db.get_item(&format!("token_{}", holding.token_address)).await {
+			Ok(Some(token)) => balances.push(TokenBalance {
+				formatted_balance: format_token_amount(&holding.balance, token.decimals.into())
+					.unwrap_or_else(|| holding.balance.clone()),
+				balance: holding.balance,
+				token,
+			}),
+			Ok(None) => {},
+			Err(err) => {
+				log::error!("Database query error: {:?}", err);
+				return HttpResponse::InternalServerError().json(ErrorResponse {
+					message: "Error retrieving token portfolio".to_string(),
+				});
+			},
+		}
+	}
+
+	HttpResponse::Ok().json(balances)
+}
@@ -1,11 +1,54 @@
-use selendra_db::{models::account::SubstrateAccount, setup_db::SurrealDb};
+use selendra_db::{
+	models::{
+		account::{EvmAccount, SubstrateAccount},
+		block::{CallCount, SubstrateBlock},
+		contract::{Contract, ContractAbi},
+		event::SubstrateEvent,
+		evm::{AccessListEntry, EvmBlockHeader, EvmLog, EvmTransaction, InternalTransaction},
+		extrinsic::SubstrateExtrinsic,
+		identity::{Identity, IdentityHistory},
+		nft::{NftBalance, NftToken},
+		offence::Offence,
+		session::{EraValidator, Session, SessionValidator},
+		stats::DailyStats,
+		token::{Token, TokenHolder, TokenTransfer},
+		transfer::SubstrateTransfer,
+	},
+	setup_db::SurrealDb,
+};
 
 use actix_web::{web, App, HttpServer};
 
-use handlers::account_handler::{get_account_by_address, get_accounts, ErrorResponse};
+use handlers::{
+	account_handler::{
+		get_account_by_address, get_account_detail, get_accounts, get_linked_account,
+		AccountBalance, AccountDetail, AccountIdentity, AccountStaking, ErrorResponse,
+		LinkedAccount,
+	},
+	block_handler::{get_block, get_blocks, get_latest_block},
+	contract_handler::{get_contract_abi, register_contract_abi, RegisterAbiRequest},
+	event_handler::{get_events, EventFilter},
+	evm_handler::{
+		get_evm_address, get_evm_blocks, get_evm_transaction, EvmActivityQuery, EvmAddressDetail,
+		EvmTransactionDetail,
+	},
+	extrinsic_handler::{get_extrinsic, get_extrinsics, ExtrinsicDetail, ExtrinsicFilter},
+	identity_handler::{get_identity_history, search_identities, IdentitySearchQuery},
+	nft_handler::{get_multi_tokens_by_owner, get_nfts_by_owner},
+	offence_handler::{get_era_slashes, get_validator_slashes},
+	producer_handler::{get_era_producers, get_validator_production},
+	search_handler::{search, SearchQuery, SearchQueryType, SearchResponse, SearchResults},
+	session_handler::{get_session_validators, get_sessions},
+	stats_handler::{get_daily_stats, DailyStatsQuery},
+	token_handler::{
+		get_token, get_token_holders, get_token_portfolio, TokenBalance, TokenDetail,
+		TokenHoldersQuery, TokenQuery,
+	},
+};
 use state::app_state::AppState;
 
 use selendra_config::CONFIG;
+use selendra_rust_client::EvmClient;
 use utils::{
 	address::AddressQuery,
 	pagination::{PaginatedResponse, PaginationParams},
@@ -22,18 +65,111 @@ mod utils;
     paths(
         handlers::account_handler::get_account_by_address,
         handlers::account_handler::get_accounts,
+        handlers::account_handler::get_linked_account,
+        handlers::account_handler::get_account_detail,
+        handlers::block_handler::get_blocks,
+        handlers::block_handler::get_latest_block,
+        handlers::block_handler::get_block,
+        handlers::extrinsic_handler::get_extrinsics,
+        handlers::extrinsic_handler::get_extrinsic,
+        handlers::event_handler::get_events,
+        handlers::identity_handler::search_identities,
+        handlers::identity_handler::get_identity_history,
+        handlers::session_handler::get_sessions,
+        handlers::session_handler::get_session_validators,
+        handlers::producer_handler::get_era_producers,
+        handlers::producer_handler::get_validator_production,
+        handlers::offence_handler::get_validator_slashes,
+        handlers::offence_handler::get_era_slashes,
+        handlers::stats_handler::get_daily_stats,
+        handlers::nft_handler::get_nfts_by_owner,
+        handlers::nft_handler::get_multi_tokens_by_owner,
+        handlers::token_handler::get_token,
+        handlers::token_handler::get_token_holders,
+        handlers::token_handler::get_token_portfolio,
+        handlers::contract_handler::register_contract_abi,
+        handlers::contract_handler::get_contract_abi,
+        handlers::search_handler::search,
+        handlers::evm_handler::get_evm_blocks,
+        handlers::evm_handler::get_evm_transaction,
+        handlers::evm_handler::get_evm_address,
     ),
     components(
         schemas(
             SubstrateAccount,
+            EvmAccount,
+            LinkedAccount,
+            AccountBalance,
+            AccountIdentity,
+            AccountStaking,
+            AccountDetail,
+            SubstrateTransfer,
             ErrorResponse,
 			PaginatedResponse<SubstrateAccount>,
+            SubstrateBlock,
+            CallCount,
+            PaginatedResponse<SubstrateBlock>,
+            SubstrateExtrinsic,
+            ExtrinsicFilter,
+            ExtrinsicDetail,
+            PaginatedResponse<SubstrateExtrinsic>,
+            SubstrateEvent,
+            EventFilter,
+            PaginatedResponse<SubstrateEvent>,
 			AddressQuery,
             PaginationParams,
+            Identity,
+            IdentityHistory,
+            IdentitySearchQuery,
+            Session,
+            SessionValidator,
+            PaginatedResponse<Session>,
+            EraValidator,
+            Offence,
+            DailyStats,
+            DailyStatsQuery,
+            NftToken,
+            NftBalance,
+            Token,
+            TokenHolder,
+            TokenQuery,
+            TokenDetail,
+            TokenHoldersQuery,
+            TokenBalance,
+            ContractAbi,
+            RegisterAbiRequest,
+            EvmTransaction,
+            AccessListEntry,
+            EvmBlockHeader,
+            PaginatedResponse<EvmBlockHeader>,
+            EvmLog,
+            InternalTransaction,
+            TokenTransfer,
+            Contract,
+            EvmActivityQuery,
+            EvmTransactionDetail,
+            EvmAddressDetail,
+            SearchQuery,
+            SearchQueryType,
+            SearchResults,
+            SearchResponse,
         )
     ),
     tags(
-        (name = "accounts", description = "Substrate Account Management API")
+        (name = "accounts", description = "Substrate Account Management API"),
+        (name = "blocks", description = "Block API"),
+        (name = "extrinsics", description = "Extrinsic API"),
+        (name = "events", description = "Event API"),
+        (name = "identities", description = "On-chain Identity API"),
+        (name = "sessions", description = "Session and Validator Set API"),
+        (name = "producers", description = "Block Producer Statistics API"),
+        (name = "slashes", description = "Slash and Offence API"),
+        (name = "stats", description = "Chain Statistics API"),
+        (name = "nfts", description = "NFT Ownership and Metadata API"),
+        (name = "tokens", description = "ERC-20 Token Registry and Holder API"),
+        (name = "contracts", description = "Contract ABI API"),
+        (name = "search", description = "Universal Search API"),
+        (name = "evm", description = "EVM Block, Transaction and Address API")
     )
 )]
 struct ApiDoc;
@@ -48,8 +184,13 @@ async fn main() -> std::io::Result<()> {
 		surreal_db_pass: CONFIG.surreal_db.pass.clone(),
 	};
 
-	// Shared application state
-	let app_state = web::Data::new(AppState::new(surreal_db));
+	// The EVM client only parses the RPC URL here; requests reach the node when a handler needs it
+	let evm_client = EvmClient::new(&CONFIG.evm_url).expect("Invalid RPC_URL");
+
+	// Shared application state. The node, used for live chain queries the archive does not
+	// index, is connected on first use so the API starts without it.
+	let app_state =
+		web::Data::new(AppState::new(surreal_db, evm_client, CONFIG.substrate_url.clone()));
 
 	HttpServer::new(move || {
 		App::new()
@@ -60,6 +201,34 @@ async fn main() -> std::io::Result<()> {
 			)
 			.route("/account", web::get().to(get_account_by_address))
 			.route("/accounts", web::get().to(get_accounts))
+			.route("/accounts/linked", web::get().to(get_linked_account))
+			.route("/accounts/{address}", web::get().to(get_account_detail))
+			.route("/accounts/{address}/tokens", web::get().to(get_token_portfolio))
+			.route("/accounts/{address}/nfts", web::get().to(get_nfts_by_owner))
+			.route("/accounts/{address}/multi-tokens", web::get().to(get_multi_tokens_by_owner))
+			.route("/blocks", web::get().to(get_blocks))
+			.route("/blocks/latest", web::get().to(get_latest_block))
+			.route("/blocks/{number_or_hash}", web::get().to(get_block))
+			.route("/extrinsics", web::get().to(get_extrinsics))
+			.route("/extrinsics/{hash}", web::get().to(get_extrinsic))
+			.route("/events", web::get().to(get_events))
+			.route("/identities/search", web::get().to(search_identities))
+			.route("/identities/{address}/history", web::get().to(get_identity_history))
+			.route("/sessions", web::get().to(get_sessions))
+			.route("/sessions/{session_index}/validators", web::get().to(get_session_validators))
+			.route("/eras/{era}/producers", web::get().to(get_era_producers))
+			.route("/validators/{address}/production", web::get().to(get_validator_production))
+			.route("/validators/{address}/slashes", web::get().to(get_validator_slashes))
+			.route("/eras/{era}/slashes", web::get().to(get_era_slashes))
+			.route("/stats/daily", web::get().to(get_daily_stats))
+			.route("/tokens/{address}", web::get().to(get_token))
+			.route("/tokens/{address}/holders", web::get().to(get_token_holders))
+			.route("/contracts/{address}/abi", web::post().to(register_contract_abi))
+			.route("/contracts/{address}/abi", web::get().to(get_contract_abi))
+			.route("/search", web::get().to(search))
+			.route("/evm/blocks", web::get().to(get_evm_blocks))
+			.route("/evm/tx/{hash}", web::get().to(get_evm_transaction))
+			.route("/evm/address/{address}", web::get().to(get_evm_address))
 	})
 	.bind((CONFIG.rest_api.url.clone(), CONFIG.rest_api.port))?
 	.run()
@@ -1,11 +1,25 @@
+use anyhow::Result;
 use selendra_db::setup_db::SurrealDb;
+use selendra_rust_client::{EvmClient, SubstrateClient};
+use tokio::sync::OnceCell;
 
 pub struct AppState {
 	pub surreal_db: SurrealDb,
+	pub evm_client: EvmClient,
+	substrate_url: String,
+	substrate_client: OnceCell<SubstrateClient>,
 }
 
 impl AppState {
-	pub fn new(surreal_db: SurrealDb) -> Self {
-		AppState { surreal_db }
+	pub fn new(surreal_db: SurrealDb, evm_client: EvmClient, substrate_url: String) -> Self {
+		AppState { surreal_db, evm_client, substrate_url, substrate_client: OnceCell::new() }
+	}
+
+	/// Node connection for live chain queries. It is opened on first use and retried on the
+	/// next request after a failure, so endpoints served from the database never need it.
+	pub async fn substrate_client(&self) -> Result<&SubstrateClient> {
+		self.substrate_client
+			.get_or_try_init(|| SubstrateClient::new(&self.substrate_url))
+			.await
 	}
 }
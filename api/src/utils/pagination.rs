@@ -69,11 +69,28 @@ impl PaginationLinks {
 		sort_by: &str,
 		sort_order: &str,
 	) -> Self {
+		Self::with_filters(base_url, page, page_size, total_pages, sort_by, sort_order, &[])
+	}
+
+	/// Links that carry the given filter parameters over to every page. Values come from the
+	/// request, so they are percent-encoded.
+	pub fn with_filters(
+		base_url: &str,
+		page: u64,
+		page_size: u64,
+		total_pages: u64,
+		sort_by: &str,
+		sort_order: &str,
+		filters: &[(&str, String)],
+	) -> Self {
+		let mut query = form_urlencoded::Serializer::new(String::new());
+		query.append_pair("sort_by", sort_by).append_pair("sort_order", sort_order);
+		for (name, value) in filters {
+			query.append_pair(name, value);
+		}
+		let query = query.finish();
 		let make_url = |p: u64| -> String {
-			format!(
-				"{}?page={}&page_size={}&sort_by={}&sort_order={}",
-				base_url, p, page_size, sort_by, sort_order
-			)
+			format!("{}?page={}&page_size={}&{}", base_url, p, page_size, query)
 		};
 
 		Self {
@@ -115,8 +115,7 @@ pub async fn run_evm_services(
 		let handle = thread::spawn(move || {
 			let rt = Runtime::new().unwrap();
 			rt.block_on(async {
-				// Use regular lock() for std::sync::Mutex
-				if let Err(e) = { service_clone.process_block_range(0, 100, None).await } {
+				if let Err(e) = service_clone.process_block().await {
 					eprintln!("Block service error: {}", e);
 				}
 			});
@@ -12,7 +12,9 @@ use service::{EvmArciveService, SubstrateArciveService};
 use tracing::info;
 use command::{run_evm_services, run_substrate_services, ArchType, Args};
 
-use selendra_rust_client::{EvmClient, SubstrateClient};
+use selendra_rust_client::{
+	chain_state::evm::metadata::HttpMetadataFetcher, EvmClient, SubstrateClient,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -35,7 +37,9 @@ async fn main() -> Result<()> {
         }
         ArchType::Evm(_) => {
             info!("Initializing EVM architecture");
-            (Some(EvmClient::new(&CONFIG.evm_url)?), None)
+            let evm_client =
+                EvmClient::new(&CONFIG.evm_url)?.with_internal_tracing(CONFIG.evm_trace_internal);
+            (Some(evm_client), None)
         }
     };
 
@@ -50,9 +54,11 @@ async fn main() -> Result<()> {
             run_substrate_services(substrate_args, service).await?;
         }
         ArchType::Evm(evm_args) => {
+			let metadata_fetcher = Arc::new(HttpMetadataFetcher::new(&CONFIG.ipfs_gateway)?);
 			let service = Arc::new(EvmArciveService::new(
 				evm_client.unwrap(),
 				surrealdb,
+				metadata_fetcher,
 			));
             run_evm_services(evm_args, service).await?;
         }
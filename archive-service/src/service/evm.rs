@@ -1,20 +1,98 @@
 use anyhow::{anyhow, Result};
+use futures::StreamExt;
+use serde_json::{json, Value};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+};
 
 use tokio::{time, time::Duration};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
+use super::stats::StatsRecorder;
 use crate::archive_state::ProcessingStats;
-use selendra_db::setup_db::SurrealDb;
-use selendra_rust_client::{models::block::EvmBlock, EvmClient};
+use selendra_db::{
+	db::{GenericDB, SortOrder},
+	models::{
+		account::{AccountLink, EvmAccount},
+		contract::{Contract, ContractAbi},
+		cursor::IndexCursor,
+		evm::{AccessListEntry, EvmBlockHeader, EvmLog, EvmTransaction, InternalTransaction},
+		nft::{NftBalance, NftToken},
+		token::{Token, TokenHolder, TokenTransfer},
+	},
+	setup_db::SurrealDb,
+};
+use selendra_rust_client::{
+	address::{evm_to_substrate_address, parse_evm_address},
+	chain_state::evm::metadata::MetadataFetcher,
+	models::{
+		block::EvmBlock,
+		contract::{ContractType, TokenTransfer as TransferEvent},
+	},
+	utils::{add_token_amounts, sub_token_amounts},
+	EvmClient,
+};
+
+const MAX_CONCURRENT_REQUESTS: usize = 5;
+// Upper bound on the transactions read back for a single block
+const MAX_BLOCK_TRANSACTIONS: u64 = 10_000;
+// Index cursor of the EVM account job
+const EVM_ACCOUNT_CURSOR: &str = "evm_account";
+
+struct EvmTables {
+	evm_block: GenericDB<EvmBlockHeader>,
+	evm_transaction: GenericDB<EvmTransaction>,
+	evm_log: GenericDB<EvmLog>,
+	internal_transaction: GenericDB<InternalTransaction>,
+	token_transfer: GenericDB<TokenTransfer>,
+	contract: GenericDB<Contract>,
+	nft_token: GenericDB<NftToken>,
+	nft_balance: GenericDB<NftBalance>,
+	token: GenericDB<Token>,
+	token_holder: GenericDB<TokenHolder>,
+	contract_abi: GenericDB<ContractAbi>,
+	stats: StatsRecorder,
+}
 
 pub struct EvmArciveService {
 	pub evm_client: EvmClient,
 	pub surreal_db: SurrealDb,
+	pub metadata_fetcher: Arc<dyn MetadataFetcher>,
 }
 
 impl EvmArciveService {
-	pub fn new(evm_client: EvmClient, surreal_db: SurrealDb) -> Self {
-		Self { evm_client, surreal_db }
+	pub fn new(
+		evm_client: EvmClient,
+		surreal_db: SurrealDb,
+		metadata_fetcher: Arc<dyn MetadataFetcher>,
+	) -> Self {
+		Self { evm_client, surreal_db, metadata_fetcher }
+	}
+
+	pub async fn process_block(&self) -> Result<()> {
+		// Resume after the last stored block, which is only written once the block is indexed
+		let start_block = self
+			.surreal_db
+			.setup_evm_block_db()
+			.await
+			.get_last_items(1, "block_number", SortOrder::Desc)
+			.await?
+			.first()
+			.map_or(0, |block| block.block_number + 1);
+		let end_block = self
+			.evm_client
+			.get_lastet_block()
+			.await?
+			.map(|block| block.block_number)
+			.ok_or_else(|| anyhow!("Latest EVM block not found"))?;
+
+		if end_block < start_block {
+			info!("EVM blocks are up to date at {}", end_block);
+			return Ok(());
+		}
+
+		self.process_block_range(start_block, end_block, None).await
 	}
 
 	pub async fn process_block_range(
@@ -27,6 +105,7 @@ impl EvmArciveService {
 			return Err(anyhow!("end_block must be greater than or equal to start_block"));
 		}
 
+		let tables = self.setup_evm_tables().await?;
 		let mut stats = ProcessingStats::new(start_block);
 		let mut current_block = start_block;
 		let rate_limit = time::interval(Duration::from_millis(rate_millis.unwrap_or(10)));
@@ -37,7 +116,7 @@ impl EvmArciveService {
 		while current_block <= end_block {
 			let mut attempt = 0;
 			let result = loop {
-				match self.process_block(current_block).await {
+				match self.index_block(&tables, current_block).await {
 					Ok(_) => break Ok(()),
 					Err(e) if attempt < max_retries => {
 						attempt += 1;
@@ -53,20 +132,17 @@ impl EvmArciveService {
 				}
 			};
 
-			match result {
-				Ok(_) => stats.processed_blocks += 1,
-				Err(e) => {
-					stats.failed_blocks += 1;
-					error!(
-						block_number = current_block,
-						error = ?e,
-						"Failed to process block after retries"
-					);
-					if stats.failed_blocks > 10 {
-						return Err(anyhow!("Too many failures, stopping processing"));
-					}
-				},
+			// Blocks after a failed one would move the resume point past it, so stop here and
+			// let the next run start again from the failed block
+			if let Err(e) = result {
+				error!(
+					block_number = current_block,
+					error = ?e,
+					"Failed to process block after retries"
+				);
+				return Err(anyhow!("Stopped at block {}: {}", current_block, e));
 			}
+			stats.processed_blocks += 1;
 
 			// Log progress periodically
 			if current_block % 100 == 0 {
@@ -80,20 +156,186 @@ impl EvmArciveService {
 		Ok(())
 	}
 
+	/// Build the EVM account table from indexed transactions, resuming after the last block
+	/// already accounted for, and refresh balance and nonce of every address touched since.
 	pub async fn process_account(&self) -> Result<()> {
-		println!("testing process account");
+		let evm_account = self.surreal_db.setup_evm_account_db().await;
+		let account_link = self.surreal_db.setup_account_link_db().await;
+		let evm_transaction = self.surreal_db.setup_evm_transaction_db().await;
+		let index_cursor = self.surreal_db.setup_index_cursor_db().await;
+
+		let start_block = index_cursor
+			.get_item(EVM_ACCOUNT_CURSOR)
+			.await?
+			.map_or(0, |cursor| cursor.block_number + 1);
+		// The block header is stored after its transactions, so only complete blocks are read
+		let end_block = self
+			.surreal_db
+			.setup_evm_block_db()
+			.await
+			.get_last_items(1, "block_number", SortOrder::Desc)
+			.await?
+			.first()
+			.map(|block| block.block_number);
+
+		if let Some(end_block) = end_block.filter(|end_block| *end_block >= start_block) {
+			for block_number in start_block..=end_block {
+				let transactions = evm_transaction
+					.get_items_by_field(
+						"block_number",
+						block_number,
+						"transaction_index",
+						SortOrder::Asc,
+						MAX_BLOCK_TRANSACTIONS,
+					)
+					.await?;
+				self.store_accounts(&evm_account, &account_link, block_number, &transactions)
+					.await?;
+
+				let cursor = IndexCursor { name: EVM_ACCOUNT_CURSOR.to_string(), block_number };
+				index_cursor.upsert_item(EVM_ACCOUNT_CURSOR, cursor).await?;
+			}
+		} else {
+			info!("EVM accounts are up to date");
+		}
+
+		Ok(())
+	}
+
+	async fn store_accounts(
+		&self,
+		evm_account: &GenericDB<EvmAccount>,
+		account_link: &GenericDB<AccountLink>,
+		block_number: u64,
+		transactions: &[EvmTransaction],
+	) -> Result<()> {
+		// Address -> (transactions in this block, is a contract)
+		let mut touched: HashMap<&str, (i64, bool)> = HashMap::new();
+		for transaction in transactions {
+			let mut addresses = HashSet::from([transaction.from_address.as_str()]);
+			if !transaction.to_address.is_empty() {
+				addresses.insert(transaction.to_address.as_str());
+				if transaction.is_contract {
+					touched.entry(transaction.to_address.as_str()).or_default().1 = true;
+				}
+			}
+			if let Some(contract_address) = &transaction.contract_address {
+				addresses.insert(contract_address.as_str());
+				touched.entry(contract_address.as_str()).or_default().1 = true;
+			}
+			for address in addresses {
+				touched.entry(address).or_default().0 += 1;
+			}
+		}
+
+		futures::stream::iter(touched)
+			.map(|(address, (transaction_count, is_contract))| {
+				self.store_account(
+					evm_account,
+					account_link,
+					block_number,
+					address,
+					transaction_count,
+					is_contract,
+				)
+			})
+			.buffer_unordered(MAX_CONCURRENT_REQUESTS)
+			.collect::<Vec<Result<()>>>()
+			.await
+			.into_iter()
+			.collect()
+	}
+
+	async fn store_account(
+		&self,
+		evm_account: &GenericDB<EvmAccount>,
+		account_link: &GenericDB<AccountLink>,
+		block_number: u64,
+		address: &str,
+		transaction_count: i64,
+		is_contract: bool,
+	) -> Result<()> {
+		let id = format!("evm_{}", address);
+		let mut fields =
+			vec![("address", json!(address)), ("last_seen_block", json!(block_number))];
+		if evm_account.get_item(&id).await?.is_none() {
+			fields.push(("first_seen_block", json!(block_number)));
+			self.store_hashed_link(account_link, address).await?;
+		}
+		if is_contract {
+			fields.push(("is_contract", json!(true)));
+		}
+
+		// Current values, so a failed lookup is simply retried the next time the address is seen
+		match futures::try_join!(
+			self.evm_client.check_balance(address, None),
+			self.evm_client.get_nonce(address)
+		) {
+			Ok((balance, nonce)) => {
+				fields.push(("balance", json!(balance.to_string())));
+				fields.push(("nonce", json!(nonce)));
+			},
+			Err(e) => warn!(address, error = ?e, "Failed to refresh EVM account balance"),
+		}
+
+		// Keyed by block, so a block retried after a partial run counts each address once
+		evm_account
+			.increment_item_once(
+				&id,
+				&format!("{}_{}", block_number, address),
+				fields,
+				vec![("transaction_count", json!(transaction_count))],
+			)
+			.await?;
+
 		Ok(())
 	}
 
-	async fn process_block(&self, block_number: u64) -> Result<()> {
+	/// Link a new EVM address to the account derived by the runtime's hashed mapping, unless
+	/// a claim already linked it
+	async fn store_hashed_link(
+		&self,
+		account_link: &GenericDB<AccountLink>,
+		address: &str,
+	) -> Result<()> {
+		let id = format!("link_{}", address);
+		if account_link.get_item(&id).await?.is_some() {
+			return Ok(());
+		}
+		if let Some(substrate_address) = evm_to_substrate_address(address) {
+			let link =
+				AccountLink { evm_address: address.to_string(), substrate_address, claimed: false };
+			account_link.upsert_item(&id, link).await?;
+		}
+		Ok(())
+	}
+
+	async fn setup_evm_tables(&self) -> Result<EvmTables> {
+		Ok(EvmTables {
+			evm_block: self.surreal_db.setup_evm_block_db().await,
+			evm_transaction: self.surreal_db.setup_evm_transaction_db().await,
+			evm_log: self.surreal_db.setup_evm_log_db().await,
+			internal_transaction: self.surreal_db.setup_internal_transaction_db().await,
+			token_transfer: self.surreal_db.setup_token_transfer_db().await,
+			contract: self.surreal_db.setup_contract_db().await,
+			nft_token: self.surreal_db.setup_nft_token_db().await,
+			nft_balance: self.surreal_db.setup_nft_balance_db().await,
+			token: self.surreal_db.setup_token_db().await,
+			token_holder: self.surreal_db.setup_token_holder_db().await,
+			contract_abi: self.surreal_db.setup_contract_abi_db().await,
+			stats: StatsRecorder::new(&self.surreal_db).await,
+		})
+	}
+
+	async fn index_block(&self, tables: &EvmTables, block_number: u64) -> Result<()> {
 		let block = self.evm_client.get_block(block_number).await?;
 		if let Some(block_data) = block {
-			self.store_block_data(block_data).await?;
+			self.store_block_data(tables, block_data).await?;
 		}
 		Ok(())
 	}
 
-	async fn store_block_data(&self, block: EvmBlock) -> Result<()> {
+	async fn store_block_data(&self, tables: &EvmTables, block: EvmBlock) -> Result<()> {
 		// Implement your storage logic here
 		info!(
 			block_number = block.block_number,
@@ -101,6 +343,545 @@ impl EvmArciveService {
 			"Storing block data"
 		);
 
+		self.load_registered_abis(tables, &block).await?;
+		self.store_transactions(tables, &block).await?;
+		self.store_logs(tables, &block).await?;
+		self.store_contracts(tables, &block).await?;
+		self.store_nft_transfers(tables, &block).await?;
+		self.store_multi_token_transfers(tables, &block).await?;
+		self.store_token_balances(tables, &block).await?;
+		tables.stats.record_evm_block(&block).await?;
+		// Stored last so the block table only points past fully indexed blocks
+		self.store_block(tables, &block).await?;
+
+		Ok(())
+	}
+
+	/// Loads the stored ABIs of contracts in the block that the shared decoder does not know
+	/// yet, so an ABI registered while the indexer runs applies from the next block
+	async fn load_registered_abis(&self, tables: &EvmTables, block: &EvmBlock) -> Result<()> {
+		let ids: Vec<String> = {
+			let decoder = self.evm_client.decoder.read().unwrap();
+			let addresses: HashSet<String> = block
+				.transactions
+				.iter()
+				.flat_map(|transaction| {
+					std::iter::once(&transaction.to)
+						.chain(transaction.logs.iter().map(|log| &log.address))
+				})
+				.filter(|address| !address.is_empty() && !decoder.is_registered(address))
+				.map(|address| format!("abi_{}", address.to_lowercase()))
+				.collect();
+			addresses.into_iter().collect()
+		};
+		if ids.is_empty() {
+			return Ok(());
+		}
+
+		let registered = tables.contract_abi.get_items_by_ids(&ids).await?;
+		let mut decoder = self.evm_client.decoder.write().unwrap();
+		for contract_abi in registered {
+			if let Err(e) = decoder.register_abi(&contract_abi.address, &contract_abi.abi) {
+				warn!(address = %contract_abi.address, error = ?e, "Skipping invalid contract ABI");
+			}
+		}
+		Ok(())
+	}
+
+	async fn store_block(&self, tables: &EvmTables, block: &EvmBlock) -> Result<()> {
+		let evm_block = EvmBlockHeader {
+			block_number: block.block_number,
+			block_hash: block.block_hash.clone(),
+			timestamp: block.timestamp,
+			transaction_count: block.transactions.len() as u32,
+			size: block.size,
+			gas_used: block.gas.gas_used,
+			gas_limit: block.gas.gas_limit,
+			base_fee_per_gas: block.gas.base_fee_per_gas.clone(),
+			burnt_fees: block.gas.burnt_fees.clone(),
+		};
+		let id = format!("block_{}", block.block_number);
+		tables.evm_block.upsert_item(&id, evm_block).await?;
+
+		Ok(())
+	}
+
+	async fn store_transactions(&self, tables: &EvmTables, block: &EvmBlock) -> Result<()> {
+		for transaction in &block.transactions {
+			let (decoded_input, error_message) = {
+				let decoder = self.evm_client.decoder.read().unwrap();
+				(
+					decoder
+						.decode_transaction(transaction)
+						.and_then(|decoded| serde_json::to_value(decoded).ok()),
+					decoder.decode_transaction_revert(transaction),
+				)
+			};
+			let evm_transaction = EvmTransaction {
+				transaction_hash: transaction.transaction_hash.clone(),
+				block_number: block.block_number,
+				timestamp: block.timestamp,
+				status: transaction.status,
+				from_address: transaction.from.clone(),
+				to_address: transaction.to.clone(),
+				is_contract: transaction.is_contract,
+				contract_address: transaction
+					.contract_creation
+					.as_ref()
+					.map(|creation| creation.address.clone()),
+				value: transaction.amount.clone(),
+				gas_price: transaction.gas_price.clone(),
+				gas_used: transaction.gas_use,
+				fee: transaction.fee.clone(),
+				max_fee_per_gas: transaction.max_fee_per_gas.clone(),
+				max_priority_fee_per_gas: transaction.max_priority_fee_per_gas.clone(),
+				effective_gas_price: transaction.effective_gas_price.clone(),
+				burnt_fee: transaction.burnt_fee.clone(),
+				priority_fee: transaction.priority_fee.clone(),
+				access_list: transaction
+					.access_list
+					.iter()
+					.map(|entry| AccessListEntry {
+						address: entry.address.clone(),
+						storage_keys: entry.storage_keys.clone(),
+					})
+					.collect(),
+				input: transaction.input.clone(),
+				decoded_input,
+				revert_data: transaction.revert_data.clone(),
+				error_message,
+				transaction_type: transaction.other.transation_type,
+				nonce: transaction.other.nonce,
+				transaction_index: transaction.other.index,
+			};
+			let id = format!("tx_{}", transaction.transaction_hash);
+			tables.evm_transaction.upsert_item(&id, evm_transaction).await?;
+
+			for call in &transaction.internal_transactions {
+				let internal_transaction = InternalTransaction {
+					transaction_hash: transaction.transaction_hash.clone(),
+					block_number: block.block_number,
+					timestamp: block.timestamp,
+					trace_index: call.index,
+					call_type: call.call_type.clone(),
+					from_address: call.from.clone(),
+					to_address: call.to.clone(),
+					value: call.value.clone(),
+					depth: call.depth,
+					error: call.error.clone(),
+				};
+				let id = format!("itx_{}_{}", transaction.transaction_hash, call.index);
+				tables.internal_transaction.upsert_item(&id, internal_transaction).await?;
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn store_contracts(&self, tables: &EvmTables, block: &EvmBlock) -> Result<()> {
+		let creations = block.transactions.iter().filter_map(|transaction| {
+			transaction.contract_creation.as_ref().map(|creation| (transaction, creation))
+		});
+
+		for (transaction, creation) in creations {
+			let address = parse_evm_address(&creation.address)?;
+			let contract_type = self
+				.evm_client
+				.contract_detector
+				.detect(address, Some(block.block_number))
+				.await?
+				.unwrap_or(ContractType::Unknown);
+
+			let contract = Contract {
+				address: creation.address.clone(),
+				creator: transaction.from.clone(),
+				creation_transaction: transaction.transaction_hash.clone(),
+				block_number: block.block_number,
+				bytecode_hash: creation.bytecode_hash.clone(),
+				contract_type: contract_type.as_str().to_string(),
+				timestamp: block.timestamp,
+			};
+			let id = format!("contract_{}", creation.address);
+			tables.contract.upsert_item(&id, contract).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Track the current owner of every ERC-721 token, resolving metadata when a token is first seen
+	async fn store_nft_transfers(&self, tables: &EvmTables, block: &EvmBlock) -> Result<()> {
+		let address_zero = self.evm_client.address_zero();
+		let nft_transfers = block
+			.transactions
+			.iter()
+			.flat_map(|transaction| &transaction.token_transfers)
+			.filter(|transfer| transfer.event == "Transfer")
+			.filter_map(|transfer| transfer.token_id.as_ref().map(|token_id| (transfer, token_id)));
+
+		for (transfer, token_id) in nft_transfers {
+			let id = format!("nft_{}_{}", transfer.token_address, token_id);
+			let is_mint = transfer.from == address_zero;
+
+			let nft_token = match tables.nft_token.get_item(&id).await? {
+				Some(mut nft_token) => {
+					nft_token.owner = transfer.to.clone();
+					nft_token.last_transfer_block = block.block_number;
+					if is_mint {
+						nft_token.mint_block = Some(block.block_number);
+					}
+					nft_token
+				},
+				None => {
+					let (token_uri, metadata) =
+						self.fetch_nft_metadata(&transfer.token_address, token_id, false).await;
+					NftToken {
+						contract_address: transfer.token_address.clone(),
+						token_id: token_id.clone(),
+						owner: transfer.to.clone(),
+						mint_block: is_mint.then_some(block.block_number),
+						last_transfer_block: block.block_number,
+						token_uri,
+						metadata,
+					}
+				},
+			};
+			tables.nft_token.upsert_item(&id, nft_token).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Apply ERC-1155 transfers to the balance of each owner and token id
+	async fn store_multi_token_transfers(
+		&self,
+		tables: &EvmTables,
+		block: &EvmBlock,
+	) -> Result<()> {
+		let address_zero = self.evm_client.address_zero();
+		let multi_token_transfers = block
+			.transactions
+			.iter()
+			.flat_map(|transaction| &transaction.token_transfers)
+			.filter(|transfer| {
+				transfer.event == "TransferSingle" || transfer.event == "TransferBatch"
+			});
+
+		for transfer in multi_token_transfers {
+			if transfer.from != address_zero {
+				self.update_nft_balance(tables, block, transfer, &transfer.from, false).await?;
+			}
+			if transfer.to != address_zero {
+				self.update_nft_balance(tables, block, transfer, &transfer.to, true).await?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Apply one side of an ERC-1155 transfer to the owner's balance, once per transfer and
+	/// side. The token's uri and metadata are resolved when an owner first holds it.
+	async fn update_nft_balance(
+		&self,
+		tables: &EvmTables,
+		block: &EvmBlock,
+		transfer: &TransferEvent,
+		owner: &str,
+		credit: bool,
+	) -> Result<()> {
+		let Some(token_id) = transfer.token_id.as_deref() else {
+			return Ok(());
+		};
+		let contract = transfer.token_address.as_str();
+		let id = format!("nft_balance_{}_{}_{}", contract, token_id, owner);
+		let side = if credit { "to" } else { "from" };
+		let key = format!(
+			"{}_{}_{}_{}",
+			block.block_number,
+			transfer.log_index,
+			transfer.batch_index.unwrap_or_default(),
+			side
+		);
+
+		let mut fields = Vec::new();
+		let balance = match tables.nft_balance.get_item(&id).await? {
+			Some(nft_balance) => nft_balance.balance,
+			None => {
+				// Other owners of the same token id already carry its metadata
+				let known = tables
+					.nft_balance
+					.get_paginated_by_filter(
+						1,
+						1,
+						vec![
+							("contract_address", "=", json!(contract)),
+							("token_id", "=", json!(token_id)),
+						],
+						&["last_transfer_block"],
+						SortOrder::Desc,
+					)
+					.await?
+					.items
+					.into_iter()
+					.next();
+				let (token_uri, metadata) = match known {
+					Some(known) => (known.token_uri, known.metadata),
+					None => self.fetch_nft_metadata(contract, token_id, true).await,
+				};
+
+				fields.push(("contract_address", json!(contract)));
+				fields.push(("token_id", json!(token_id)));
+				fields.push(("owner", json!(owner)));
+				if let Some(token_uri) = token_uri {
+					fields.push(("token_uri", json!(token_uri)));
+				}
+				if let Some(metadata) = metadata {
+					fields.push(("metadata", metadata));
+				}
+				"0".to_string()
+			},
+		};
+
+		let new_balance = if credit {
+			add_token_amounts(&balance, &transfer.value)
+		} else {
+			sub_token_amounts(&balance, &transfer.value)
+		};
+		let new_balance = new_balance.unwrap_or_else(|| {
+			warn!(
+				contract,
+				token_id,
+				owner,
+				%balance,
+				value = %transfer.value,
+				"Invalid NFT balance update"
+			);
+			balance.clone()
+		});
+		fields.push(("balance", json!(new_balance)));
+		fields.push(("last_transfer_block", json!(block.block_number)));
+
+		tables.nft_balance.increment_item_once(&id, &key, fields, vec![]).await
+	}
+
+	/// Register new ERC-20 tokens and apply their transfers to holder balances. Each balance
+	/// change is recorded once per transfer, so indexing a block again leaves balances unchanged.
+	async fn store_token_balances(&self, tables: &EvmTables, block: &EvmBlock) -> Result<()> {
+		let address_zero = self.evm_client.address_zero();
+		let erc20_transfers = block
+			.transactions
+			.iter()
+			.flat_map(|transaction| &transaction.token_transfers)
+			.filter(|transfer| transfer.event == "Transfer" && transfer.token_id.is_none());
+
+		// Holder counts and, after mints and burns, the supply are refreshed once per token
+		let mut registered = HashSet::new();
+		let mut supply_changed = HashSet::new();
+		for transfer in erc20_transfers {
+			let token_address = transfer.token_address.as_str();
+			// Balances are still tracked for a token that could not be read yet; it is
+			// registered again on its next transfer
+			match self.register_token(tables, token_address).await {
+				Ok(()) => {
+					registered.insert(token_address);
+				},
+				Err(e) => warn!(token_address, error = ?e, "Failed to register token"),
+			}
+
+			if transfer.from == address_zero || transfer.to == address_zero {
+				supply_changed.insert(token_address);
+			}
+			if transfer.from != address_zero {
+				self.update_holder_balance(tables, block, transfer, &transfer.from, false)
+					.await?;
+			}
+			if transfer.to != address_zero {
+				self.update_holder_balance(tables, block, transfer, &transfer.to, true).await?;
+			}
+		}
+
+		for token_address in registered {
+			let holder_count = self.count_holders(tables, token_address).await?;
+			let mut fields = vec![("holder_count", json!(holder_count))];
+			// Non-standard tokens may revert on totalSupply(), which keeps the stored supply
+			if supply_changed.contains(token_address) {
+				match self.evm_client.get_token_total_supply(token_address).await {
+					Ok(total_supply) => fields.push(("total_supply", json!(total_supply))),
+					Err(e) => warn!(token_address, error = ?e, "Failed to refresh total supply"),
+				}
+			}
+			tables
+				.token
+				.increment_item(&format!("token_{}", token_address), fields, vec![])
+				.await?;
+		}
+
+		Ok(())
+	}
+
+	/// Read name, symbol, decimals and supply the first time a token is seen
+	async fn register_token(&self, tables: &EvmTables, token_address: &str) -> Result<()> {
+		let id = format!("token_{}", token_address);
+		if tables.token.get_item(&id).await?.is_some() {
+			return Ok(());
+		}
+
+		let info = self.evm_client.get_token_info(token_address).await?;
+		let token = Token {
+			address: token_address.to_string(),
+			name: info.name,
+			symbol: info.symbol,
+			decimals: info.decimals,
+			total_supply: info.total_supply,
+			holder_count: 0,
+		};
+		tables.token.upsert_item(&id, token).await?;
+
+		Ok(())
+	}
+
+	/// Apply one side of a transfer to the holder's balance, once per transfer and side
+	async fn update_holder_balance(
+		&self,
+		tables: &EvmTables,
+		block: &EvmBlock,
+		transfer: &TransferEvent,
+		holder: &str,
+		credit: bool,
+	) -> Result<()> {
+		let token_address = transfer.token_address.as_str();
+		let value = transfer.value.as_str();
+		let id = format!("holder_{}_{}", token_address, holder);
+		let side = if credit { "to" } else { "from" };
+		let key = format!("{}_{}_{}", block.block_number, transfer.log_index, side);
+		let balance = tables
+			.token_holder
+			.get_item(&id)
+			.await?
+			.map(|token_holder| token_holder.balance)
+			.unwrap_or_else(|| "0".to_string());
+
+		let new_balance = if credit {
+			add_token_amounts(&balance, value)
+		} else {
+			sub_token_amounts(&balance, value)
+		};
+		// An unreadable amount or an overflowing credit leaves the balance as it was rather
+		// than failing the block on every retry
+		let new_balance = new_balance.unwrap_or_else(|| {
+			warn!(token_address, holder, %balance, value, "Invalid token balance update");
+			balance.clone()
+		});
+
+		tables
+			.token_holder
+			.increment_item_once(
+				&id,
+				&key,
+				vec![
+					("token_address", json!(token_address)),
+					("holder", json!(holder)),
+					("balance", json!(new_balance)),
+					("last_updated_block", json!(block.block_number)),
+				],
+				vec![],
+			)
+			.await
+	}
+
+	/// Holders with a non-zero balance, counted from the stored balances
+	async fn count_holders(&self, tables: &EvmTables, token_address: &str) -> Result<u64> {
+		tables
+			.token_holder
+			.count_by_filter(vec![
+				("token_address", "=", json!(token_address)),
+				("balance", "!=", json!("0")),
+			])
+			.await
+	}
+
+	/// Metadata is best effort: an unreachable URI must not stop indexing the block
+	async fn fetch_nft_metadata(
+		&self,
+		contract: &str,
+		token_id: &str,
+		multi_token: bool,
+	) -> (Option<String>, Option<Value>) {
+		// ERC-1155 names the getter `uri`, ERC-721 `tokenURI`
+		let token_uri = if multi_token {
+			self.evm_client.get_multi_token_uri(contract, token_id).await
+		} else {
+			self.evm_client.get_nft_token_uri(contract, token_id).await
+		};
+		let token_uri = match token_uri {
+			Ok(token_uri) => token_uri,
+			Err(e) => {
+				warn!(contract, token_id, error = ?e, "Failed to read token URI");
+				None
+			},
+		};
+
+		let metadata = match &token_uri {
+			Some(uri) => match self.metadata_fetcher.fetch(uri).await {
+				Ok(metadata) if metadata.is_object() => Some(metadata),
+				Ok(_) => None,
+				Err(e) => {
+					warn!(contract, token_id, uri = %uri, error = ?e, "Failed to fetch NFT metadata");
+					None
+				},
+			},
+			None => None,
+		};
+
+		(token_uri, metadata)
+	}
+
+	async fn store_logs(&self, tables: &EvmTables, block: &EvmBlock) -> Result<()> {
+		for transaction in &block.transactions {
+			for log in &transaction.logs {
+				let evm_log = EvmLog {
+					transaction_hash: transaction.transaction_hash.clone(),
+					block_number: block.block_number,
+					log_index: log.log_index,
+					address: log.address.clone(),
+					topics: log.topics.clone(),
+					data: log.data.clone(),
+					decoded: self
+						.evm_client
+						.decoder
+						.read()
+						.unwrap()
+						.decode_evm_log(log)
+						.and_then(|decoded| serde_json::to_value(decoded).ok()),
+				};
+				let id = format!("log_{}_{}", block.block_number, log.log_index);
+				tables.evm_log.upsert_item(&id, evm_log).await?;
+			}
+
+			for transfer in &transaction.token_transfers {
+				let token_transfer = TokenTransfer {
+					transaction_hash: transaction.transaction_hash.clone(),
+					block_number: block.block_number,
+					log_index: transfer.log_index,
+					token_address: transfer.token_address.clone(),
+					event: transfer.event.clone(),
+					from_address: transfer.from.clone(),
+					to_address: transfer.to.clone(),
+					value: transfer.value.clone(),
+					token_id: transfer.token_id.clone(),
+					batch_index: transfer.batch_index,
+					timestamp: block.timestamp,
+				};
+				let id = match transfer.batch_index {
+					Some(batch_index) => format!(
+						"transfer_{}_{}_{}",
+						block.block_number, transfer.log_index, batch_index
+					),
+					None => format!("transfer_{}_{}", block.block_number, transfer.log_index),
+				};
+				tables.token_transfer.upsert_item(&id, token_transfer).await?;
+			}
+		}
+
 		Ok(())
 	}
 }
@@ -1,4 +1,5 @@
 pub mod evm;
+pub mod stats;
 pub mod substrate;
 
 pub use evm::*;
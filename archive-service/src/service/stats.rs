@@ -0,0 +1,160 @@
+use anyhow::Result;
+use serde_json::json;
+use std::collections::HashSet;
+
+use selendra_db::{
+	db::GenericDB,
+	models::stats::{DailyAccount, DailyStats},
+	setup_db::SurrealDb,
+};
+use selendra_rust_client::models::block::{BlockDetail, EvmBlock};
+
+const MILLIS_PER_DAY: u64 = 86_400_000;
+
+/// Rolls indexed blocks up into per-day counters so charts never scan the raw tables.
+pub struct StatsRecorder {
+	daily_stats: GenericDB<DailyStats>,
+	daily_account: GenericDB<DailyAccount>,
+}
+
+impl StatsRecorder {
+	pub async fn new(surreal_db: &SurrealDb) -> Self {
+		Self {
+			daily_stats: surreal_db.setup_daily_stats_db().await,
+			daily_account: surreal_db.setup_daily_account_db().await,
+		}
+	}
+
+	pub async fn record_substrate_block(&self, block: &BlockDetail) -> Result<()> {
+		// Substrate timestamps are in milliseconds
+		let date = date_from_millis(block.timestamp);
+		let block_key = format!("substrate_{}", block.block_number);
+
+		let signers: HashSet<&str> = block
+			.extrinsics
+			.extrinsic
+			.iter()
+			.filter(|extrinsic| extrinsic.is_signed && !extrinsic.signer.is_empty())
+			.map(|extrinsic| extrinsic.signer.as_str())
+			.collect();
+		let active_accounts = self.count_new_daily_accounts(&date, &block_key, signers).await?;
+
+		let events = &block.events.extrinsic;
+		let transfers = events.iter().filter(|event| event.transfer_event.is_some()).count();
+		let new_accounts = events.iter().filter(|event| event.new_account_event.is_some()).count();
+		let fees_paid: u128 =
+			block.extrinsics.extrinsic.iter().map(|extrinsic| extrinsic.fee).sum();
+
+		// Fee totals are decimal strings, added to inside the same transaction as the counters
+		self.daily_stats
+			.increment_item_once(
+				&stats_id(&date),
+				&block_key,
+				vec![("date", json!(date))],
+				vec![
+					("fees_paid", json!(fees_paid.to_string())),
+					("blocks", json!(1)),
+					("extrinsics", json!(block.extrinsics.extrinsic.len())),
+					("transfers", json!(transfers)),
+					("active_accounts", json!(active_accounts)),
+					("new_accounts", json!(new_accounts)),
+				],
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	pub async fn record_evm_block(&self, block: &EvmBlock) -> Result<()> {
+		// EVM timestamps are in seconds
+		let date = date_from_millis(block.timestamp * 1000);
+		let block_key = format!("evm_{}", block.block_number);
+
+		let senders: HashSet<&str> = block.transactions.iter().map(|tx| tx.from.as_str()).collect();
+		let active_accounts = self.count_new_daily_accounts(&date, &block_key, senders).await?;
+
+		self.daily_stats
+			.increment_item_once(
+				&stats_id(&date),
+				&block_key,
+				vec![("date", json!(date))],
+				vec![
+					("evm_fees_burned", json!(block.gas.burnt_fees)),
+					("evm_transactions", json!(block.transactions.len())),
+					("evm_gas_used", json!(block.gas.gas_used)),
+					("active_accounts", json!(active_accounts)),
+				],
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	/// Record the accounts seen on `date` and return how many `block_key` saw first that day.
+	/// Accounts keep the block that first saw them, so recording a block again gives the same
+	/// count.
+	async fn count_new_daily_accounts(
+		&self,
+		date: &str,
+		block_key: &str,
+		addresses: HashSet<&str>,
+	) -> Result<u64> {
+		let mut new_accounts = 0;
+		for address in addresses {
+			let id = format!("{}_{}", stats_id(date), address);
+			match self.daily_account.get_item(&id).await? {
+				Some(account) if account.first_seen != block_key => {},
+				Some(_) => new_accounts += 1,
+				None => {
+					let account = DailyAccount {
+						date: date.to_string(),
+						address: address.to_string(),
+						first_seen: block_key.to_string(),
+					};
+					self.daily_account.upsert_item(&id, account).await?;
+					new_accounts += 1;
+				},
+			}
+		}
+		Ok(new_accounts)
+	}
+}
+
+fn stats_id(date: &str) -> String {
+	format!("day_{}", date.replace('-', ""))
+}
+
+/// Convert a unix timestamp in milliseconds to a UTC `YYYY-MM-DD` date.
+fn date_from_millis(timestamp: u64) -> String {
+	// Civil-from-days algorithm by Howard Hinnant
+	let days = (timestamp / MILLIS_PER_DAY) as i64 + 719_468;
+	let era = days.div_euclid(146_097);
+	let day_of_era = days.rem_euclid(146_097);
+	let year_of_era =
+		(day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+	let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+	format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_date_from_millis() {
+		assert_eq!(date_from_millis(0), "1970-01-01");
+		assert_eq!(date_from_millis(951_782_400_000), "2000-02-29");
+		assert_eq!(date_from_millis(1_735_689_599_999), "2024-12-31");
+		assert_eq!(date_from_millis(1_735_689_600_000), "2025-01-01");
+	}
+
+	#[test]
+	fn test_stats_id() {
+		assert_eq!(stats_id("2024-12-31"), "day_20241231");
+	}
+}
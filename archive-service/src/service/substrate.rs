@@ -1,17 +1,50 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use futures::StreamExt;
+use serde_json::{json, Value};
 use std::collections::HashSet;
-use tokio::time::{sleep, Duration};
-use tracing::info;
+use tokio::{
+	time,
+	time::{sleep, Duration},
+};
+use tracing::{error, info};
 
+use super::stats::StatsRecorder;
+use crate::archive_state::ProcessingStats;
 use selendra_config::CONFIG;
-use selendra_db::{models::account::SubstrateAccount, setup_db::SurrealDb};
-use selendra_rust_client::SubstrateClient;
+use selendra_db::{
+	db::{GenericDB, SortOrder},
+	models::{
+		account::{AccountLink, SubstrateAccount},
+		block::{CallCount, SubstrateBlock},
+		event::SubstrateEvent,
+		extrinsic::SubstrateExtrinsic,
+		identity::{Identity, IdentityHistory},
+		offence::Offence,
+		session::{EraValidator, Session, SessionValidator},
+		transfer::SubstrateTransfer,
+	},
+	setup_db::SurrealDb,
+};
+use selendra_rust_client::{models::block::BlockDetail, SubstrateClient};
 
 const MAX_RETRIES: u32 = 3;
 const INITIAL_BACKOFF_MS: u64 = 1000; // 1 second
 const MAX_CONCURRENT_REQUESTS: usize = 5; // Limit concurrent request
 
+struct BlockTables {
+	block: GenericDB<SubstrateBlock>,
+	extrinsic: GenericDB<SubstrateExtrinsic>,
+	event: GenericDB<SubstrateEvent>,
+	transfer: GenericDB<SubstrateTransfer>,
+	identity: GenericDB<Identity>,
+	identity_history: GenericDB<IdentityHistory>,
+	session: GenericDB<Session>,
+	session_validator: GenericDB<SessionValidator>,
+	era_validator: GenericDB<EraValidator>,
+	offence: GenericDB<Offence>,
+	stats: StatsRecorder,
+}
+
 pub struct SubstrateArciveService {
 	pub substrate_client: SubstrateClient,
 	pub surreal_db: SurrealDb,
@@ -23,12 +56,85 @@ impl SubstrateArciveService {
 	}
 
 	pub async fn process_block(&self) -> Result<()> {
-		println!("testing process block");
+		// Resume after the last stored block so aggregates are not counted twice
+		let start_block = self
+			.surreal_db
+			.setup_block_db()
+			.await
+			.get_last_items(1, "block_number", SortOrder::Desc)
+			.await?
+			.first()
+			.map_or(0, |block| block.block_number + 1);
+		let end_block = self.substrate_client.get_finalized_block_number().await?;
+
+		if end_block < start_block {
+			info!("Substrate blocks are up to date at {}", end_block);
+			return Ok(());
+		}
+
+		self.process_block_range(start_block, end_block, None).await
+	}
+
+	pub async fn process_block_range(
+		&self,
+		start_block: u32,
+		end_block: u32,
+		rate_millis: Option<u64>,
+	) -> Result<()> {
+		if end_block < start_block {
+			return Err(anyhow!("end_block must be greater than or equal to start_block"));
+		}
+
+		let tables = self.setup_block_tables().await;
+		let mut stats = ProcessingStats::new(start_block.into());
+		let mut current_block = start_block;
+		let mut rate_limiter = time::interval(Duration::from_millis(rate_millis.unwrap_or(10)));
+
+		while current_block <= end_block {
+			let mut attempt = 0;
+			let result = loop {
+				match self.index_block(&tables, current_block).await {
+					Ok(_) => break Ok(()),
+					Err(e) if attempt < MAX_RETRIES => {
+						attempt += 1;
+						error!(
+							block_number = current_block,
+							attempt,
+							error = ?e,
+							"Error processing block, retrying"
+						);
+						sleep(Duration::from_millis(100 * 2_u64.pow(attempt))).await;
+					},
+					Err(e) => break Err(e),
+				}
+			};
+
+			// Blocks after a failed one would move the resume point past it, so stop here and
+			// let the next run start again from the failed block
+			if let Err(e) = result {
+				error!(
+					block_number = current_block,
+					error = ?e,
+					"Failed to process block after retries"
+				);
+				return Err(anyhow!("Stopped at block {}: {}", current_block, e));
+			}
+			stats.processed_blocks += 1;
+
+			if current_block % 100 == 0 {
+				stats.log_progress(current_block.into(), end_block.into());
+			}
+
+			current_block += 1;
+			rate_limiter.tick().await;
+		}
+
 		Ok(())
 	}
 
 	pub async fn process_account(&self) -> Result<()> {
 		let db = self.surreal_db.setup_account_db().await;
+		let account_link = self.surreal_db.setup_account_link_db().await;
 
 		let mut accounts = self.substrate_client.get_all_accounts().await?;
 		accounts = self.remove_duplicates(accounts);
@@ -36,8 +142,11 @@ impl SubstrateArciveService {
 		futures::stream::iter(accounts)
 			.map(|account| {
 				let db = db.clone();
+				let account_link = account_link.clone();
 
 				async move {
+					self.store_claimed_link(&account_link, &account).await;
+
 					match self.check_balance_with_retry(&account, MAX_RETRIES).await {
 						Ok(Some(balance)) => {
 							let id = format!("account_{}", account);
@@ -62,6 +171,317 @@ impl SubstrateArciveService {
 		Ok(())
 	}
 
+	/// Link the account to the EVM address it claimed, replacing any hashed mapping
+	async fn store_claimed_link(&self, account_link: &GenericDB<AccountLink>, account: &str) {
+		match self.substrate_client.ss58_to_evm(account).await {
+			Ok(Some(evm_address)) => {
+				let link = AccountLink {
+					evm_address: evm_address.clone(),
+					substrate_address: account.to_string(),
+					claimed: true,
+				};
+				if let Err(e) =
+					account_link.upsert_item(&format!("link_{}", evm_address), link).await
+				{
+					info!("Failed to link account {}: {:?}", account, e);
+				}
+			},
+			Ok(None) => {},
+			Err(e) => info!("Error fetching claimed EVM address for {}: {:?}", account, e),
+		}
+	}
+
+	async fn setup_block_tables(&self) -> BlockTables {
+		BlockTables {
+			block: self.surreal_db.setup_block_db().await,
+			extrinsic: self.surreal_db.setup_extrinsic_db().await,
+			event: self.surreal_db.setup_event_db().await,
+			transfer: self.surreal_db.setup_transfer_db().await,
+			identity: self.surreal_db.setup_identity_db().await,
+			identity_history: self.surreal_db.setup_identity_history_db().await,
+			session: self.surreal_db.setup_session_db().await,
+			session_validator: self.surreal_db.setup_session_validator_db().await,
+			era_validator: self.surreal_db.setup_era_validator_db().await,
+			offence: self.surreal_db.setup_offence_db().await,
+			stats: StatsRecorder::new(&self.surreal_db).await,
+		}
+	}
+
+	async fn index_block(&self, tables: &BlockTables, block_number: u32) -> Result<()> {
+		if let Some(block) = self.substrate_client.get_block(block_number).await? {
+			self.store_extrinsics(tables, &block).await?;
+			self.store_events(tables, &block).await?;
+			self.store_transfers(tables, &block).await?;
+			self.store_identity_events(tables, &block).await?;
+			self.store_session_data(tables, &block).await?;
+			self.store_block_producer(tables, &block).await?;
+			self.store_offence_events(tables, &block).await?;
+			tables.stats.record_substrate_block(&block).await?;
+			// Stored last so the block table only points past fully indexed blocks
+			self.store_block(tables, &block).await?;
+		}
+		Ok(())
+	}
+
+	async fn store_block(&self, tables: &BlockTables, block: &BlockDetail) -> Result<()> {
+		let substrate_block = SubstrateBlock {
+			block_number: block.block_number,
+			block_hash: block.block_hash.clone(),
+			parent_hash: block.parent_hash.clone(),
+			extrinsics_root: block.extrinsics_root.clone(),
+			state_root: block.state_root.clone(),
+			author: block.author.clone(),
+			era: block.activ_era,
+			session_index: block.session_index,
+			spec_name: block.runtime_version.spec_name.clone(),
+			spec_version: block.runtime_version.spec_version,
+			transaction_version: block.runtime_version.transaction_version,
+			extrinsic_count: block.extrinsics.total,
+			event_count: block.events.total,
+			extrinsic_summary: count_calls(
+				block
+					.extrinsics
+					.extrinsic
+					.iter()
+					.map(|extrinsic| (&extrinsic.pallet, &extrinsic.method)),
+			),
+			event_summary: count_calls(
+				block.events.extrinsic.iter().map(|event| (&event.pallet, &event.method)),
+			),
+			timestamp: block.timestamp,
+		};
+		let id = format!("block_{}", block.block_number);
+		tables.block.upsert_item(&id, substrate_block).await?;
+
+		Ok(())
+	}
+
+	async fn store_extrinsics(&self, tables: &BlockTables, block: &BlockDetail) -> Result<()> {
+		for extrinsic in &block.extrinsics.extrinsic {
+			let substrate_extrinsic = SubstrateExtrinsic {
+				block_number: block.block_number,
+				extrinsic_index: extrinsic.index.into(),
+				hash: extrinsic.hash.clone(),
+				pallet: extrinsic.pallet.clone(),
+				method: extrinsic.method.clone(),
+				call: extrinsic.runtime_call.clone(),
+				signer: Some(extrinsic.signer.clone()).filter(|signer| !signer.is_empty()),
+				fee: extrinsic.fee.to_string(),
+				success: extrinsic.success,
+				error: extrinsic.error.clone(),
+				timestamp: block.timestamp,
+			};
+			let id = format!("extrinsic_{}_{}", block.block_number, extrinsic.index);
+			tables.extrinsic.upsert_item(&id, substrate_extrinsic).await?;
+		}
+
+		Ok(())
+	}
+
+	async fn store_events(&self, tables: &BlockTables, block: &BlockDetail) -> Result<()> {
+		for event in &block.events.extrinsic {
+			// Signer and outcome of the parent extrinsic are kept on the event to filter by
+			let extrinsic = event.extrinsic_id.and_then(|extrinsic_id| {
+				block
+					.extrinsics
+					.extrinsic
+					.iter()
+					.find(|extrinsic| u32::from(extrinsic.index) == extrinsic_id)
+			});
+			let substrate_event = SubstrateEvent {
+				block_number: block.block_number,
+				event_index: event.index,
+				extrinsic_index: event.extrinsic_id,
+				signer: extrinsic
+					.map(|extrinsic| extrinsic.signer.clone())
+					.filter(|signer| !signer.is_empty()),
+				success: extrinsic.map(|extrinsic| extrinsic.success),
+				pallet: event.pallet.clone(),
+				method: event.method.clone(),
+				data: event.event.clone(),
+				timestamp: block.timestamp,
+			};
+			let id = format!("event_{}_{}", block.block_number, event.index);
+			tables.event.upsert_item(&id, substrate_event).await?;
+		}
+
+		Ok(())
+	}
+
+	async fn store_transfers(&self, tables: &BlockTables, block: &BlockDetail) -> Result<()> {
+		for event in &block.events.extrinsic {
+			if let Some(transfer) = &event.transfer_event {
+				let substrate_transfer = SubstrateTransfer {
+					block_number: block.block_number,
+					event_index: event.index,
+					extrinsic_index: event.extrinsic_id,
+					from_address: transfer.from.clone(),
+					to_address: transfer.to.clone(),
+					amount: transfer.amount.to_string(),
+					timestamp: block.timestamp,
+				};
+				let id = format!("transfer_{}_{}", block.block_number, event.index);
+				tables.transfer.upsert_item(&id, substrate_transfer).await?;
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn store_block_producer(&self, tables: &BlockTables, block: &BlockDetail) -> Result<()> {
+		if let Some(author) = &block.author {
+			let id = format!("era_{}_{}", block.activ_era, author);
+			// The block itself is stored last, so it is not counted yet
+			let blocks_produced = self
+				.count_authored(tables, block, author, vec![("era", "=", json!(block.activ_era))])
+				.await? + 1;
+			let era_validator =
+				EraValidator { era: block.activ_era, validator: author.clone(), blocks_produced };
+			tables.era_validator.upsert_item(&id, era_validator).await?;
+		}
+
+		Ok(())
+	}
+
+	async fn store_session_data(&self, tables: &BlockTables, block: &BlockDetail) -> Result<()> {
+		let new_session =
+			block.events.extrinsic.iter().find_map(|event| event.new_session_event.as_ref());
+
+		if let Some(new_session) = new_session {
+			let session_validators =
+				self.substrate_client.get_session_validators(Some(block.block_number)).await?;
+			let session = Session {
+				session_index: new_session.session_index,
+				era: block.activ_era,
+				start_block: block.block_number,
+				timestamp: block.timestamp,
+				validators: session_validators.validators,
+				queued_validators: session_validators.queued_validators,
+			};
+			let id = format!("session_{}", new_session.session_index);
+			tables.session.upsert_item(&id, session).await?;
+		}
+
+		// The block enacting a new session was still produced by the previous validator set
+		let author_session = match new_session {
+			Some(_) => block.session_index.saturating_sub(1),
+			None => block.session_index,
+		};
+
+		if let Some(author) = &block.author {
+			let id = format!("session_{}_{}", author_session, author);
+			// Blocks carrying the session, except the one enacting it, which the previous set
+			// produced
+			let mut filters = vec![("session_index", "=", json!(author_session))];
+			if let Some(start_block) = self.session_start(tables, author_session).await? {
+				filters.push(("block_number", "!=", json!(start_block)));
+			}
+			let mut blocks_produced =
+				self.count_authored(tables, block, author, filters).await? + 1;
+
+			// The block enacting the next session was produced in this one; it is only stored
+			// already when the session is indexed again
+			if let Some(next_start) = self.session_start(tables, author_session + 1).await? {
+				let filters = vec![("block_number", "=", json!(next_start))];
+				blocks_produced += self.count_authored(tables, block, author, filters).await?;
+			}
+
+			let session_validator = SessionValidator {
+				session_index: author_session,
+				validator: author.clone(),
+				blocks_produced,
+			};
+			tables.session_validator.upsert_item(&id, session_validator).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Stored blocks of `author` matching `filters`, other than `block`. Counting the block table
+	/// rather than incrementing keeps the totals right when a block is indexed again.
+	async fn count_authored(
+		&self,
+		tables: &BlockTables,
+		block: &BlockDetail,
+		author: &str,
+		mut filters: Vec<(&str, &str, Value)>,
+	) -> Result<u32> {
+		filters.push(("author", "=", json!(author)));
+		filters.push(("block_number", "!=", json!(block.block_number)));
+		Ok(tables.block.count_by_filter(filters).await? as u32)
+	}
+
+	async fn session_start(&self, tables: &BlockTables, session_index: u32) -> Result<Option<u32>> {
+		let session = tables.session.get_item(&format!("session_{}", session_index)).await?;
+		Ok(session.map(|session| session.start_block))
+	}
+
+	async fn store_identity_events(&self, tables: &BlockTables, block: &BlockDetail) -> Result<()> {
+		for event in &block.events.extrinsic {
+			let Some(identity_event) = &event.identity_event else {
+				continue;
+			};
+
+			let history = IdentityHistory {
+				substrate_address: identity_event.who.clone(),
+				event: identity_event.event.clone(),
+				registrar_index: identity_event.registrar_index,
+				block_number: block.block_number,
+				event_index: event.index,
+				timestamp: block.timestamp,
+			};
+			let history_id = format!("identity_{}_{}", block.block_number, event.index);
+			tables.identity_history.upsert_item(&history_id, history).await?;
+
+			// Always refresh from chain state so the table holds the current identity
+			let id = format!("identity_{}", identity_event.who);
+			match self.substrate_client.get_account_identity(&identity_event.who).await? {
+				Some(identity) => {
+					let identity = Identity {
+						substrate_address: identity_event.who.clone(),
+						display_name: identity.display_name,
+						legal_name: identity.legal_name,
+						web: identity.web,
+						riot: identity.riot,
+						email: identity.email,
+						twitter: identity.twitter,
+						image: identity.image,
+						judgement: identity.judgement,
+						updated_at_block: block.block_number,
+					};
+					tables.identity.upsert_item(&id, identity).await?;
+				},
+				None => {
+					tables.identity.delete_item(&id).await?;
+				},
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn store_offence_events(&self, tables: &BlockTables, block: &BlockDetail) -> Result<()> {
+		for event in &block.events.extrinsic {
+			for (position, offence_event) in event.offence_events.iter().enumerate() {
+				let offence = Offence {
+					event: offence_event.event.clone(),
+					offender: offence_event.offender.clone(),
+					reporter: offence_event.reporter.clone(),
+					amount: offence_event.amount.map(|amount| amount.to_string()),
+					era: offence_event.era.unwrap_or(block.activ_era),
+					details: offence_event.details.clone(),
+					block_number: block.block_number,
+					event_index: event.index,
+					timestamp: block.timestamp,
+				};
+				let id = format!("offence_{}_{}_{}", block.block_number, event.index, position);
+				tables.offence.upsert_item(&id, offence).await?;
+			}
+		}
+
+		Ok(())
+	}
+
 	async fn check_balance_with_retry(
 		&self,
 		account: &str,
@@ -130,3 +550,19 @@ impl SubstrateArciveService {
 		(balance as f64) / divisor
 	}
 }
+
+/// Occurrences of each pallet call or event, in order of first appearance
+fn count_calls<'a>(calls: impl Iterator<Item = (&'a String, &'a String)>) -> Vec<CallCount> {
+	let mut counts: Vec<CallCount> = Vec::new();
+	for (pallet, method) in calls {
+		match counts
+			.iter_mut()
+			.find(|count| &count.pallet == pallet && &count.method == method)
+		{
+			Some(count) => count.count += 1,
+			None =>
+				counts.push(CallCount { pallet: pallet.clone(), method: method.clone(), count: 1 }),
+		}
+	}
+	counts
+}
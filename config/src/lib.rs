@@ -14,6 +14,8 @@ pub struct Config {
 	pub evm_url: String,
 	pub substrate_url: String,
 	pub chain_decimal: u16,
+	pub ipfs_gateway: String,
+	pub evm_trace_internal: bool,
 	pub surreal_db: SurrealDbConfig,
 	pub rest_api: AxtixWebConfig,
 }
@@ -26,6 +28,32 @@ pub struct SurrealDbConfig {
 	pub namespace: String,
 	pub database: String,
 	pub account_table: String,
+	pub identity_table: String,
+	pub identity_history_table: String,
+	pub session_table: String,
+	pub session_validator_table: String,
+	pub era_validator_table: String,
+	pub block_table: String,
+	pub extrinsic_table: String,
+	pub event_table: String,
+	pub transfer_table: String,
+	pub offence_table: String,
+	pub daily_stats_table: String,
+	pub daily_account_table: String,
+	pub evm_block_table: String,
+	pub evm_transaction_table: String,
+	pub evm_log_table: String,
+	pub token_transfer_table: String,
+	pub token_table: String,
+	pub token_holder_table: String,
+	pub contract_table: String,
+	pub contract_abi_table: String,
+	pub nft_token_table: String,
+	pub nft_balance_table: String,
+	pub internal_transaction_table: String,
+	pub evm_account_table: String,
+	pub account_link_table: String,
+	pub index_cursor_table: String,
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +72,13 @@ impl Config {
 				.unwrap_or("18")
 				.parse::<u16>()
 				.expect("Should be number: 18"),
+			ipfs_gateway: env::var("IPFS_GATEWAY")
+				.unwrap_or_else(|_| "https://ipfs.io/ipfs".to_string()),
+			evm_trace_internal: env::var("EVM_TRACE_INTERNAL")
+				.as_deref()
+				.unwrap_or("false")
+				.parse::<bool>()
+				.expect("Should be bool: false"),
 			surreal_db: SurrealDbConfig::load_from_env(),
 			rest_api: AxtixWebConfig::load_from_env(),
 		}
@@ -61,6 +96,55 @@ impl SurrealDbConfig {
 				.unwrap_or_else(|_| "selendra_explorer".to_string()),
 			account_table: env::var("SURREALDB_ACCOUNT_TABLE")
 				.unwrap_or_else(|_| "account".to_string()),
+			identity_table: env::var("SURREALDB_IDENTITY_TABLE")
+				.unwrap_or_else(|_| "identity".to_string()),
+			identity_history_table: env::var("SURREALDB_IDENTITY_HISTORY_TABLE")
+				.unwrap_or_else(|_| "identity_history".to_string()),
+			session_table: env::var("SURREALDB_SESSION_TABLE")
+				.unwrap_or_else(|_| "session".to_string()),
+			session_validator_table: env::var("SURREALDB_SESSION_VALIDATOR_TABLE")
+				.unwrap_or_else(|_| "session_validator".to_string()),
+			era_validator_table: env::var("SURREALDB_ERA_VALIDATOR_TABLE")
+				.unwrap_or_else(|_| "era_validator".to_string()),
+			block_table: env::var("SURREALDB_BLOCK_TABLE").unwrap_or_else(|_| "block".to_string()),
+			extrinsic_table: env::var("SURREALDB_EXTRINSIC_TABLE")
+				.unwrap_or_else(|_| "extrinsic".to_string()),
+			event_table: env::var("SURREALDB_EVENT_TABLE").unwrap_or_else(|_| "event".to_string()),
+			transfer_table: env::var("SURREALDB_TRANSFER_TABLE")
+				.unwrap_or_else(|_| "transfer".to_string()),
+			offence_table: env::var("SURREALDB_OFFENCE_TABLE")
+				.unwrap_or_else(|_| "offence".to_string()),
+			daily_stats_table: env::var("SURREALDB_DAILY_STATS_TABLE")
+				.unwrap_or_else(|_| "daily_stats".to_string()),
+			daily_account_table: env::var("SURREALDB_DAILY_ACCOUNT_TABLE")
+				.unwrap_or_else(|_| "daily_account".to_string()),
+			evm_block_table: env::var("SURREALDB_EVM_BLOCK_TABLE")
+				.unwrap_or_else(|_| "evm_block".to_string()),
+			evm_transaction_table: env::var("SURREALDB_EVM_TRANSACTION_TABLE")
+				.unwrap_or_else(|_| "evm_transaction".to_string()),
+			evm_log_table: env::var("SURREALDB_EVM_LOG_TABLE")
+				.unwrap_or_else(|_| "evm_log".to_string()),
+			token_transfer_table: env::var("SURREALDB_TOKEN_TRANSFER_TABLE")
+				.unwrap_or_else(|_| "token_transfer".to_string()),
+			token_table: env::var("SURREALDB_TOKEN_TABLE").unwrap_or_else(|_| "token".to_string()),
+			token_holder_table: env::var("SURREALDB_TOKEN_HOLDER_TABLE")
+				.unwrap_or_else(|_| "token_holder".to_string()),
+			contract_table: env::var("SURREALDB_CONTRACT_TABLE")
+				.unwrap_or_else(|_| "contract".to_string()),
+			contract_abi_table: env::var("SURREALDB_CONTRACT_ABI_TABLE")
+				.unwrap_or_else(|_| "contract_abi".to_string()),
+			nft_token_table: env::var("SURREALDB_NFT_TOKEN_TABLE")
+				.unwrap_or_else(|_| "nft_token".to_string()),
+			nft_balance_table: env::var("SURREALDB_NFT_BALANCE_TABLE")
+				.unwrap_or_else(|_| "nft_balance".to_string()),
+			internal_transaction_table: env::var("SURREALDB_INTERNAL_TRANSACTION_TABLE")
+				.unwrap_or_else(|_| "internal_transaction".to_string()),
+			evm_account_table: env::var("SURREALDB_EVM_ACCOUNT_TABLE")
+				.unwrap_or_else(|_| "evm_account".to_string()),
+			account_link_table: env::var("SURREALDB_ACCOUNT_LINK_TABLE")
+				.unwrap_or_else(|_| "account_link".to_string()),
+			index_cursor_table: env::var("SURREALDB_INDEX_CURSOR_TABLE")
+				.unwrap_or_else(|_| "index_cursor".to_string()),
 		}
 	}
 }
@@ -3,6 +3,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use surrealdb::{
 	engine::remote::ws::{Client, Ws},
 	opt::auth::Root,
+	sql::Thing,
 	Surreal,
 };
 use tracing::info;
@@ -71,6 +72,71 @@ where
 		self.execute_with_content("UPDATE", id, item, "Updated").await
 	}
 
+	// Create the item or replace it if the ID already exists
+	pub async fn upsert_item(&self, id: &str, item: T) -> Result<Option<T>> {
+		self.execute_with_content("UPSERT", id, item, "Upserted").await
+	}
+
+	// Add to numeric fields and set the others, creating the item if the ID does not exist.
+	// Runs as a single statement so concurrent writers do not lose increments.
+	pub async fn increment_item(
+		&self,
+		id: &str,
+		fields: Vec<(&str, serde_json::Value)>,
+		increments: Vec<(&str, serde_json::Value)>,
+	) -> Result<Option<T>> {
+		let query = format!(
+			"UPSERT $record SET {} RETURN AFTER;",
+			increment_assignments(&fields, &increments)?
+		);
+
+		let mut request = self.db.query(&query).bind(("record", self.record(id)));
+		for (index, (_, value)) in fields.into_iter().enumerate() {
+			request = request.bind((format!("field_{}", index), value));
+		}
+		for (index, (_, value)) in increments.into_iter().enumerate() {
+			request = request.bind((format!("increment_{}", index), value));
+		}
+
+		request.await.map_err(|e| anyhow!(e))?.take(0).map_err(|e| anyhow!(e))
+	}
+
+	// Like increment_item, but applies the change only once per `key`. The key is recorded in
+	// `<table>_applied` in the same transaction, so applying it again changes nothing.
+	pub async fn increment_item_once(
+		&self,
+		id: &str,
+		key: &str,
+		fields: Vec<(&str, serde_json::Value)>,
+		increments: Vec<(&str, serde_json::Value)>,
+	) -> Result<()> {
+		let query = format!(
+			"BEGIN TRANSACTION;
+			IF (SELECT * FROM $applied) = [] {{
+				CREATE $applied;
+				UPSERT $record SET {};
+			}};
+			COMMIT TRANSACTION;",
+			increment_assignments(&fields, &increments)?
+		);
+		let applied = Thing::from((format!("{}_applied", self.table).as_str(), key));
+
+		let mut request = self
+			.db
+			.query(&query)
+			.bind(("record", self.record(id)))
+			.bind(("applied", applied));
+		for (index, (_, value)) in fields.into_iter().enumerate() {
+			request = request.bind((format!("field_{}", index), value));
+		}
+		for (index, (_, value)) in increments.into_iter().enumerate() {
+			request = request.bind((format!("increment_{}", index), value));
+		}
+
+		request.await?.check()?;
+		Ok(())
+	}
+
 	// Delete a single item by ID
 	pub async fn delete_item(&self, id: &str) -> Result<Option<T>> {
 		let deleted: Option<T> = self.db.delete((self.table.as_str(), id)).await?;
@@ -82,22 +148,33 @@ where
 	}
 
 	pub async fn insert_items(&self, items: Vec<BatchInsertItem<T>>) -> Result<Vec<T>> {
+		let records: Vec<Thing> = items.iter().map(|item| self.record(&item.id)).collect();
 		let mut query = String::from("BEGIN TRANSACTION;\n");
 
-		for item in items.iter() {
-			query.push_str(&format!(
-				"CREATE {}:{} CONTENT {};\n",
-				self.table,
-				item.id,
-				serde_json::to_string(&item.data)?
-			));
+		for index in 0..items.len() {
+			query.push_str(&format!("CREATE $record_{} CONTENT $content_{};\n", index, index));
 		}
 
 		query.push_str("COMMIT TRANSACTION;\n");
-		self.db.query(query).await?;
 
-		let ids: Vec<String> = items.iter().map(|item| item.id.clone()).collect();
-		self.fetch_items_by_ids(ids).await
+		let mut request = self.db.query(query);
+		for (index, (record, item)) in records.iter().cloned().zip(items).enumerate() {
+			request = request
+				.bind((format!("record_{}", index), record))
+				.bind((format!("content_{}", index), item.data));
+		}
+		request.await?;
+
+		self.fetch_items_by_ids(records).await
+	}
+
+	pub async fn get_item(&self, id: &str) -> Result<Option<T>> {
+		self.db.select((self.table.as_str(), id)).await.map_err(|e| anyhow!(e))
+	}
+
+	/// Records with the given ids; ids without a record are skipped
+	pub async fn get_items_by_ids(&self, ids: &[String]) -> Result<Vec<T>> {
+		self.fetch_items_by_ids(ids.iter().map(|id| self.record(id)).collect()).await
 	}
 
 	pub async fn get_item_by_field(
@@ -105,10 +182,66 @@ where
 		field: &str,
 		value: impl Serialize + 'static,
 	) -> Result<Option<T>> {
-		let query = format!("SELECT * FROM {} WHERE {} = $value LIMIT 1;", self.table, field);
+		let query =
+			format!("SELECT * FROM {} WHERE {} = $value LIMIT 1;", self.table, identifier(field)?);
+		self.db
+			.query(&query)
+			.bind(("value", value))
+			.await
+			.map_err(|e| anyhow!(e))?
+			.take(0)
+			.map_err(|e| anyhow!(e))
+	}
+
+	pub async fn get_items_by_field(
+		&self,
+		field: &str,
+		value: impl Serialize + 'static,
+		sort_by: &str,
+		order: SortOrder,
+		limit: u64,
+	) -> Result<Vec<T>> {
+		let query = format!(
+			"SELECT * FROM {} WHERE {} = $value ORDER BY {} {} LIMIT $limit;",
+			self.table,
+			identifier(field)?,
+			identifier(sort_by)?,
+			order.as_str()
+		);
 		self.db
 			.query(&query)
 			.bind(("value", value))
+			.bind(("limit", limit))
+			.await
+			.map_err(|e| anyhow!(e))?
+			.take(0)
+			.map_err(|e| anyhow!(e))
+	}
+
+	// Case-insensitive match on the starts of the words of a string field. The field needs a
+	// full-text index with the `name_search` analyzer defined in setup_db.
+	pub async fn search_by_field(&self, field: &str, term: &str, limit: u64) -> Result<Vec<T>> {
+		let query = format!(
+			"SELECT * FROM {} WHERE {} @@ $term LIMIT $limit;",
+			self.table,
+			identifier(field)?
+		);
+		self.db
+			.query(&query)
+			.bind(("term", term.to_string()))
+			.bind(("limit", limit))
+			.await
+			.map_err(|e| anyhow!(e))?
+			.take(0)
+			.map_err(|e| anyhow!(e))
+	}
+
+	// Fetch every item; only meant for small tables
+	pub async fn get_all_items(&self) -> Result<Vec<T>> {
+		let query = format!("SELECT * FROM {};", self.table);
+
+		self.db
+			.query(&query)
 			.await
 			.map_err(|e| anyhow!(e))?
 			.take(0)
@@ -124,7 +257,7 @@ where
 		let query = format!(
 			"SELECT * FROM {} ORDER BY {} {} LIMIT $limit;",
 			self.table,
-			field,
+			identifier(field)?,
 			order.as_str()
 		);
 
@@ -168,14 +301,11 @@ where
 		let total = self.get_total_count().await?;
 		let total_pages = (total + page_size - 1) / page_size;
 
-		let order = match sort_order {
-			SortOrder::Asc => "ASC",
-			SortOrder::Desc => "DESC",
-		};
-
 		let query = format!(
 			"SELECT * FROM {} ORDER BY {} {} LIMIT $limit START $start;",
-			self.table, sort_by, order
+			self.table,
+			identifier(sort_by)?,
+			sort_order.as_str()
 		);
 
 		let items: Vec<T> = self
@@ -189,18 +319,96 @@ where
 		Ok(PaginatedResult { items, total, page, page_size, total_pages })
 	}
 
+	// Items matching every (field, operator, value) condition, ordered by each sort field in turn
+	pub async fn get_items_by_filter(
+		&self,
+		filters: Vec<(&str, &str, serde_json::Value)>,
+		sort_by: &[&str],
+		sort_order: SortOrder,
+		limit: u64,
+	) -> Result<Vec<T>> {
+		let query = format!(
+			"SELECT * FROM {}{} ORDER BY {} LIMIT $limit;",
+			self.table,
+			where_clause(&filters)?,
+			order_clause(sort_by, sort_order)?
+		);
+
+		let mut request = self.db.query(&query).bind(("limit", limit));
+		for (index, (_, _, value)) in filters.into_iter().enumerate() {
+			request = request.bind((format!("filter_{}", index), value));
+		}
+
+		request.await.map_err(|e| anyhow!(e))?.take(0).map_err(|e| anyhow!(e))
+	}
+
+	// Number of items matching every (field, operator, value) condition
+	pub async fn count_by_filter(
+		&self,
+		filters: Vec<(&str, &str, serde_json::Value)>,
+	) -> Result<u64> {
+		let query =
+			format!("SELECT count() FROM {}{} GROUP ALL;", self.table, where_clause(&filters)?);
+
+		let mut request = self.db.query(&query);
+		for (index, (_, _, value)) in filters.into_iter().enumerate() {
+			request = request.bind((format!("filter_{}", index), value));
+		}
+
+		let count_result: Vec<CountResult> = request.await?.take(0)?;
+		Ok(count_result.first().map(|r| r.count).unwrap_or(0))
+	}
+
+	// Paginate over the items matching every (field, operator, value) condition
+	pub async fn get_paginated_by_filter(
+		&self,
+		page: u64,
+		page_size: u64,
+		filters: Vec<(&str, &str, serde_json::Value)>,
+		sort_by: &[&str],
+		sort_order: SortOrder,
+	) -> Result<PaginatedResult<T>> {
+		let offset = (page - 1) * page_size;
+		let condition = where_clause(&filters)?;
+
+		let query = format!(
+			"SELECT count() FROM {table}{condition} GROUP ALL;
+			SELECT * FROM {table}{condition} ORDER BY {order} LIMIT $limit START $start;",
+			table = self.table,
+			order = order_clause(sort_by, sort_order)?
+		);
+
+		let mut request = self.db.query(&query).bind(("limit", page_size)).bind(("start", offset));
+		for (index, (_, _, value)) in filters.into_iter().enumerate() {
+			request = request.bind((format!("filter_{}", index), value));
+		}
+
+		let mut response = request.await?;
+		let count_result: Vec<CountResult> = response.take(0)?;
+		let items: Vec<T> = response.take(1)?;
+
+		let total = count_result.first().map(|r| r.count).unwrap_or(0);
+		let total_pages = (total + page_size - 1) / page_size;
+
+		Ok(PaginatedResult { items, total, page, page_size, total_pages })
+	}
+
 	// Helper Methods
+	// Record ids are bound as parameters rather than formatted into queries
+	fn record(&self, id: &str) -> Thing {
+		Thing::from((self.table.as_str(), id))
+	}
+
 	async fn get_total_count(&self) -> Result<u64> {
 		let query = format!("SELECT VALUE count() FROM {} GROUP ALL;", self.table);
 		let count_result: Vec<CountResult> = self.db.query(&query).await?.take(0)?;
 		Ok(count_result.first().map(|r| r.count).unwrap_or(0))
 	}
 
-	async fn fetch_items_by_ids(&self, ids: Vec<String>) -> Result<Vec<T>> {
-		let query = format!("SELECT * FROM {} WHERE id IN $ids;", self.table);
+	async fn fetch_items_by_ids(&self, records: Vec<Thing>) -> Result<Vec<T>> {
 		self.db
-			.query(&query)
-			.bind(("ids", ids))
+			.query("SELECT * FROM $records;")
+			.bind(("records", records))
 			.await
 			.map_err(|e| anyhow!(e))?
 			.take(0)
@@ -214,8 +422,14 @@ where
 		item: T,
 		log_message: &str,
 	) -> Result<Option<T>> {
-		let query = format!("{} {}:{} CONTENT $content RETURN *;", action, self.table, id);
-		let result: Option<T> = self.db.query(&query).bind(("content", item)).await?.take(0)?;
+		let query = format!("{} $record CONTENT $content RETURN *;", action);
+		let result: Option<T> = self
+			.db
+			.query(&query)
+			.bind(("record", self.record(id)))
+			.bind(("content", item))
+			.await?
+			.take(0)?;
 
 		if result.is_some() {
 			info!("{} item with ID: {}", log_message, id);
@@ -224,6 +438,68 @@ where
 	}
 }
 
+const COMPARISON_OPERATORS: [&str; 6] = ["=", "!=", "<", "<=", ">", ">="];
+
+// ` WHERE field op $filter_0 AND ...`, empty without conditions
+fn where_clause(filters: &[(&str, &str, serde_json::Value)]) -> Result<String> {
+	if filters.is_empty() {
+		return Ok(String::new());
+	}
+
+	let mut conditions = Vec::new();
+	for (index, (field, operator, _)) in filters.iter().enumerate() {
+		if !COMPARISON_OPERATORS.contains(operator) {
+			return Err(anyhow!("Invalid comparison operator: {}", operator));
+		}
+		conditions.push(format!("{} {} $filter_{}", identifier(field)?, operator, index));
+	}
+	Ok(format!(" WHERE {}", conditions.join(" AND ")))
+}
+
+// `field = $field_0, counter = (counter ?? 0) + $increment_0, ...`. Increments given as
+// decimal strings are added to a decimal string field, for amounts that overflow an int.
+fn increment_assignments(
+	fields: &[(&str, serde_json::Value)],
+	increments: &[(&str, serde_json::Value)],
+) -> Result<String> {
+	let mut assignments = Vec::new();
+	for (index, (field, _)) in fields.iter().enumerate() {
+		assignments.push(format!("{} = $field_{}", identifier(field)?, index));
+	}
+	for (index, (field, value)) in increments.iter().enumerate() {
+		let field = identifier(field)?;
+		let assignment = if value.is_string() {
+			// Drop the suffix a decimal may carry when cast to a string
+			format!(
+				"{field} = string::replace(<string>(<decimal>({field} ?? \"0\") + \
+				 <decimal>$increment_{index}), \"dec\", \"\")"
+			)
+		} else {
+			format!("{} = ({} ?? 0) + $increment_{}", field, field, index)
+		};
+		assignments.push(assignment);
+	}
+	Ok(assignments.join(", "))
+}
+
+// Field names cannot be bound as parameters, so only plain identifiers are let into queries
+fn identifier(field: &str) -> Result<&str> {
+	let valid = !field.is_empty() && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+	if valid {
+		Ok(field)
+	} else {
+		Err(anyhow!("Invalid field name: {}", field))
+	}
+}
+
+fn order_clause(sort_by: &[&str], sort_order: SortOrder) -> Result<String> {
+	let mut order = Vec::new();
+	for field in sort_by {
+		order.push(format!("{} {}", identifier(field)?, sort_order.as_str()));
+	}
+	Ok(order.join(", "))
+}
+
 impl SortOrder {
 	fn as_str(&self) -> &'static str {
 		match self {
@@ -444,4 +720,34 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_query_clauses_reject_unsafe_names() {
+		assert_eq!(
+			where_clause(&[("number", ">=", serde_json::json!(1))]).unwrap(),
+			" WHERE number >= $filter_0"
+		);
+		assert!(where_clause(&[("number; DELETE test_items", "=", serde_json::json!(1))]).is_err());
+		assert!(where_clause(&[("number", "= 1 OR true OR", serde_json::json!(1))]).is_err());
+
+		assert_eq!(
+			order_clause(&["number", "name"], SortOrder::Asc).unwrap(),
+			"number ASC, name ASC"
+		);
+		assert!(order_clause(&["number DESC, name"], SortOrder::Asc).is_err());
+	}
+
+	#[test]
+	fn test_increment_assignments() {
+		let assignments = increment_assignments(
+			&[("date", serde_json::json!("2024-12-31"))],
+			&[("blocks", serde_json::json!(1)), ("fees", serde_json::json!("5"))],
+		)
+		.unwrap();
+		assert_eq!(
+			assignments,
+			"date = $field_0, blocks = (blocks ?? 0) + $increment_0, fees = string::replace(\
+			 <string>(<decimal>(fees ?? \"0\") + <decimal>$increment_1), \"dec\", \"\")"
+		);
+	}
 }
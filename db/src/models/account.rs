@@ -14,3 +14,31 @@ pub struct SubstrateAccount {
 	/// lock balance
 	pub lock: f64,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct EvmAccount {
+	/// EVM address
+	pub address: String,
+	/// native balance in wei, uint256 in decimal
+	pub balance: String,
+	/// number of transactions sent
+	pub nonce: u64,
+	/// whether the address holds contract code
+	pub is_contract: bool,
+	/// first indexed block the address took part in
+	pub first_seen_block: u64,
+	/// latest indexed block the address took part in
+	pub last_seen_block: u64,
+	/// number of indexed transactions sent or received
+	pub transaction_count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct AccountLink {
+	/// EVM address
+	pub evm_address: String,
+	/// Substrate address the EVM address maps to
+	pub substrate_address: String,
+	/// whether the link is a claim in the EVM accounts pallet rather than the hashed mapping
+	pub claimed: bool,
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct SubstrateBlock {
+	/// block number
+	pub block_number: u32,
+	/// block hash
+	pub block_hash: String,
+	/// parent block hash
+	pub parent_hash: String,
+	/// extrinsics root
+	pub extrinsics_root: String,
+	/// state root
+	pub state_root: String,
+	/// block author substrate address
+	pub author: Option<String>,
+	/// active era
+	pub era: u32,
+	/// session index
+	pub session_index: u32,
+	/// runtime spec name
+	pub spec_name: String,
+	/// runtime spec version
+	pub spec_version: u32,
+	/// runtime transaction version
+	pub transaction_version: u32,
+	/// number of extrinsics
+	pub extrinsic_count: u32,
+	/// number of events
+	pub event_count: u32,
+	/// extrinsics per call, in order of first appearance
+	pub extrinsic_summary: Vec<CallCount>,
+	/// events per event type, in order of first appearance
+	pub event_summary: Vec<CallCount>,
+	/// block timestamp
+	pub timestamp: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct CallCount {
+	/// pallet name
+	pub pallet: String,
+	/// call or event name
+	pub method: String,
+	/// occurrences in the block
+	pub count: u32,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct Contract {
+	/// deployed contract address
+	pub address: String,
+	/// account that sent the creation transaction
+	pub creator: String,
+	/// creation transaction hash
+	pub creation_transaction: String,
+	/// block the contract was deployed in
+	pub block_number: u64,
+	/// keccak256 of the deployed bytecode
+	pub bytecode_hash: String,
+	/// detected standard: ERC20, ERC721 or Unknown
+	pub contract_type: String,
+	/// block timestamp
+	pub timestamp: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct ContractAbi {
+	/// contract address
+	pub address: String,
+	/// optional contract name
+	pub name: Option<String>,
+	/// JSON ABI
+	pub abi: String,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// Last block a resumable job has fully processed, so its next run starts after it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IndexCursor {
+	/// job name, e.g. `evm_account`
+	pub name: String,
+	/// last block processed
+	pub block_number: u64,
+}
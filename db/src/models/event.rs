@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct SubstrateEvent {
+	/// block number
+	pub block_number: u32,
+	/// index within the block
+	pub event_index: u32,
+	/// index of the emitting extrinsic, none for block initialization and finalization
+	pub extrinsic_index: Option<u32>,
+	/// signer of the emitting extrinsic, none when it is unsigned or there is none
+	pub signer: Option<String>,
+	/// whether the emitting extrinsic dispatched successfully, none without one
+	pub success: Option<bool>,
+	/// pallet name
+	pub pallet: String,
+	/// event name
+	pub method: String,
+	/// decoded event with its fields
+	pub data: String,
+	/// block timestamp
+	pub timestamp: u64,
+}
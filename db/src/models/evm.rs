@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct EvmBlockHeader {
+	/// block number
+	pub block_number: u64,
+	/// block hash
+	pub block_hash: String,
+	/// block timestamp in seconds
+	pub timestamp: u64,
+	/// number of transactions
+	pub transaction_count: u32,
+	/// block size in bytes
+	pub size: u64,
+	/// gas used by all transactions
+	pub gas_used: u64,
+	/// block gas limit
+	pub gas_limit: u64,
+	/// base fee per gas in wei, uint256 in decimal
+	pub base_fee_per_gas: String,
+	/// base fee burnt by the block in wei, uint256 in decimal
+	pub burnt_fees: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct EvmLog {
+	/// transaction hash
+	pub transaction_hash: String,
+	/// block number
+	pub block_number: u64,
+	/// log index within the block
+	pub log_index: u64,
+	/// emitting contract address
+	pub address: String,
+	/// indexed topics, topic0 is the event signature
+	pub topics: Vec<String>,
+	/// non-indexed data, hex encoded
+	pub data: String,
+	/// event name and parameters, when a known ABI matched
+	#[schema(value_type = Option<Object>)]
+	pub decoded: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct EvmTransaction {
+	/// transaction hash
+	pub transaction_hash: String,
+	/// block number
+	pub block_number: u64,
+	/// block timestamp in seconds
+	pub timestamp: u64,
+	/// whether the transaction succeeded
+	pub status: bool,
+	/// sender address
+	pub from_address: String,
+	/// recipient address, empty for contract creations
+	pub to_address: String,
+	/// whether the recipient is a contract
+	pub is_contract: bool,
+	/// address deployed by a contract creation
+	pub contract_address: Option<String>,
+	/// transferred value in wei, uint256 in decimal
+	pub value: String,
+	/// gas price in wei, uint256 in decimal
+	pub gas_price: String,
+	/// gas used by the transaction
+	pub gas_used: u64,
+	/// fee paid in wei, uint256 in decimal
+	pub fee: String,
+	/// EIP-1559 fee cap in wei, uint256 in decimal
+	pub max_fee_per_gas: Option<String>,
+	/// EIP-1559 priority fee cap in wei, uint256 in decimal
+	pub max_priority_fee_per_gas: Option<String>,
+	/// price actually paid per gas in wei, uint256 in decimal
+	pub effective_gas_price: String,
+	/// part of the fee burnt by the base fee, uint256 in decimal
+	pub burnt_fee: String,
+	/// part of the fee paid to the block author, uint256 in decimal
+	pub priority_fee: String,
+	/// EIP-2930 access list
+	pub access_list: Vec<AccessListEntry>,
+	/// EIP-2718 transaction type
+	pub transaction_type: u32,
+	/// call data, hex encoded
+	pub input: String,
+	/// called function name and parameters, when a known ABI matched
+	#[schema(value_type = Option<Object>)]
+	pub decoded_input: Option<Value>,
+	/// revert data of a failed transaction, hex encoded
+	pub revert_data: Option<String>,
+	/// decoded revert reason of a failed transaction
+	pub error_message: Option<String>,
+	/// sender nonce
+	pub nonce: u64,
+	/// index within the block
+	pub transaction_index: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct AccessListEntry {
+	/// accessed address
+	pub address: String,
+	/// accessed storage keys
+	pub storage_keys: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct InternalTransaction {
+	/// hash of the enclosing transaction
+	pub transaction_hash: String,
+	/// block number
+	pub block_number: u64,
+	/// block timestamp in seconds
+	pub timestamp: u64,
+	/// position within the transaction's call tree, depth first
+	pub trace_index: u32,
+	/// CALL, DELEGATECALL, STATICCALL, CREATE, CREATE2 or SELFDESTRUCT
+	pub call_type: String,
+	/// calling address
+	pub from_address: String,
+	/// called or created address
+	pub to_address: String,
+	/// transferred value in wei, uint256 in decimal
+	pub value: String,
+	/// call depth, 1 for calls made directly by the transaction
+	pub depth: u32,
+	/// error of a failed call
+	pub error: Option<String>,
+}
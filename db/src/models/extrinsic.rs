@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct SubstrateExtrinsic {
+	/// block number
+	pub block_number: u32,
+	/// index within the block
+	pub extrinsic_index: u32,
+	/// extrinsic hash
+	pub hash: String,
+	/// pallet name
+	pub pallet: String,
+	/// call name
+	pub method: String,
+	/// decoded call with its arguments
+	pub call: String,
+	/// signer substrate address, none for unsigned extrinsics
+	pub signer: Option<String>,
+	/// fee paid in plancks, u128 in decimal
+	pub fee: String,
+	/// whether the extrinsic dispatched successfully
+	pub success: bool,
+	/// dispatch error of a failed extrinsic
+	pub error: Option<String>,
+	/// block timestamp
+	pub timestamp: u64,
+}
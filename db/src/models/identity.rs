@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct Identity {
+	/// substrate_address
+	pub substrate_address: String,
+	/// display name
+	pub display_name: Option<String>,
+	/// legal name
+	pub legal_name: Option<String>,
+	/// website
+	pub web: Option<String>,
+	/// riot/matrix handle
+	pub riot: Option<String>,
+	/// email
+	pub email: Option<String>,
+	/// twitter handle
+	pub twitter: Option<String>,
+	/// image
+	pub image: Option<String>,
+	/// registrar judgement
+	pub judgement: Option<String>,
+	/// block of the last identity change
+	pub updated_at_block: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct IdentityHistory {
+	/// substrate_address
+	pub substrate_address: String,
+	/// identity event name (IdentitySet, IdentityCleared, IdentityKilled, JudgementGiven)
+	pub event: String,
+	/// registrar index for judgement events
+	pub registrar_index: Option<u32>,
+	/// block number
+	pub block_number: u32,
+	/// event index within the block
+	pub event_index: u32,
+	/// block timestamp
+	pub timestamp: u64,
+}
@@ -1 +1,14 @@
 pub mod account;
+pub mod block;
+pub mod contract;
+pub mod cursor;
+pub mod event;
+pub mod evm;
+pub mod extrinsic;
+pub mod identity;
+pub mod nft;
+pub mod offence;
+pub mod session;
+pub mod stats;
+pub mod token;
+pub mod transfer;
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct NftToken {
+	/// ERC-721 contract address
+	pub contract_address: String,
+	/// token id as a decimal string
+	pub token_id: String,
+	/// current owner, the zero address once burned
+	pub owner: String,
+	/// block the token was minted in, if the mint was indexed
+	pub mint_block: Option<u64>,
+	/// block of the latest transfer
+	pub last_transfer_block: u64,
+	/// tokenURI returned by the contract
+	pub token_uri: Option<String>,
+	/// JSON metadata fetched from the token URI
+	#[schema(value_type = Option<Object>)]
+	pub metadata: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct NftBalance {
+	/// ERC-1155 contract address
+	pub contract_address: String,
+	/// token id as a decimal string
+	pub token_id: String,
+	/// holder of the tokens
+	pub owner: String,
+	/// amount held as a decimal string, "0" once everything is transferred away
+	pub balance: String,
+	/// block of the latest transfer to or from the owner
+	pub last_transfer_block: u64,
+	/// uri returned by the contract, with `{id}` substituted
+	pub token_uri: Option<String>,
+	/// JSON metadata fetched from the token URI
+	#[schema(value_type = Option<Object>)]
+	pub metadata: Option<Value>,
+}
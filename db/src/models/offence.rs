@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct Offence {
+	/// offence event name (Slashed, SlashReported, Chilled, BanValidators)
+	pub event: String,
+	/// offending validator or nominator substrate address
+	pub offender: String,
+	/// reporter substrate address, when the runtime records one
+	pub reporter: Option<String>,
+	/// slashed amount in base units, u128 in decimal
+	pub amount: Option<String>,
+	/// era the offence applies to
+	pub era: u32,
+	/// slash fraction or ban reason
+	pub details: Option<String>,
+	/// block number
+	pub block_number: u32,
+	/// event index within the block
+	pub event_index: u32,
+	/// block timestamp
+	pub timestamp: u64,
+}
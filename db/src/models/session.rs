@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct Session {
+	/// session index
+	pub session_index: u32,
+	/// era the session belongs to
+	pub era: u32,
+	/// first block of the session
+	pub start_block: u32,
+	/// timestamp of the first block
+	pub timestamp: u64,
+	/// active validator set
+	pub validators: Vec<String>,
+	/// validators queued for the next session
+	pub queued_validators: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct SessionValidator {
+	/// session index
+	pub session_index: u32,
+	/// validator substrate address
+	pub validator: String,
+	/// number of blocks authored in the session
+	pub blocks_produced: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct EraValidator {
+	/// era index
+	pub era: u32,
+	/// validator substrate address
+	pub validator: String,
+	/// number of blocks authored in the era
+	pub blocks_produced: u32,
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct DailyStats {
+	/// day in YYYY-MM-DD (UTC)
+	pub date: String,
+	/// substrate blocks
+	pub blocks: u64,
+	/// substrate extrinsics
+	pub extrinsics: u64,
+	/// balance transfers
+	pub transfers: u64,
+	/// distinct signers and EVM senders
+	pub active_accounts: u64,
+	/// accounts created
+	pub new_accounts: u64,
+	/// substrate transaction fees paid by extrinsics in plancks, u128 in decimal
+	pub fees_paid: String,
+	/// EVM transactions
+	pub evm_transactions: u64,
+	/// EVM gas used
+	pub evm_gas_used: u64,
+	/// EVM base fee burned (base fee * gas used) in wei, u128 in decimal
+	pub evm_fees_burned: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DailyAccount {
+	/// day in YYYY-MM-DD (UTC)
+	pub date: String,
+	/// substrate or EVM address
+	pub address: String,
+	/// block the address was first seen in that day, e.g. `substrate_42` or `evm_42`
+	pub first_seen: String,
+}
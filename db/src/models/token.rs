@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct TokenTransfer {
+	/// transaction hash
+	pub transaction_hash: String,
+	/// block number
+	pub block_number: u64,
+	/// log index within the block
+	pub log_index: u64,
+	/// token contract address
+	pub token_address: String,
+	/// Transfer, Approval, TransferSingle or TransferBatch
+	pub event: String,
+	/// sender, or owner for Approval
+	pub from_address: String,
+	/// recipient, or spender for Approval
+	pub to_address: String,
+	/// raw token amount as a decimal string
+	pub value: String,
+	/// ERC-721 or ERC-1155 token id as a decimal string
+	pub token_id: Option<String>,
+	/// position within an ERC-1155 TransferBatch
+	pub batch_index: Option<u32>,
+	/// block timestamp
+	pub timestamp: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct Token {
+	/// token contract address
+	pub address: String,
+	/// token name
+	pub name: String,
+	/// token symbol
+	pub symbol: String,
+	/// number of decimals of the raw amounts
+	pub decimals: u8,
+	/// raw total supply as a decimal string
+	pub total_supply: String,
+	/// number of addresses with a non-zero balance
+	pub holder_count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct TokenHolder {
+	/// token contract address
+	pub token_address: String,
+	/// holder address
+	pub holder: String,
+	/// raw balance as a decimal string
+	pub balance: String,
+	/// block of the latest balance change
+	pub last_updated_block: u64,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct SubstrateTransfer {
+	/// block number
+	pub block_number: u32,
+	/// index of the Transfer event within the block
+	pub event_index: u32,
+	/// index of the extrinsic that made the transfer
+	pub extrinsic_index: Option<u32>,
+	/// sender substrate address
+	pub from_address: String,
+	/// recipient substrate address
+	pub to_address: String,
+	/// amount in plancks, u128 in decimal
+	pub amount: String,
+	/// block timestamp
+	pub timestamp: u64,
+}
@@ -1,9 +1,31 @@
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::{db::GenericDB, models::account::SubstrateAccount};
+use crate::{
+	db::GenericDB,
+	models::{
+		account::{AccountLink, EvmAccount, SubstrateAccount},
+		block::SubstrateBlock,
+		contract::{Contract, ContractAbi},
+		cursor::IndexCursor,
+		event::SubstrateEvent,
+		evm::{EvmBlockHeader, EvmLog, EvmTransaction, InternalTransaction},
+		extrinsic::SubstrateExtrinsic,
+		identity::{Identity, IdentityHistory},
+		nft::{NftBalance, NftToken},
+		offence::Offence,
+		session::{EraValidator, Session, SessionValidator},
+		stats::{DailyAccount, DailyStats},
+		token::{Token, TokenHolder, TokenTransfer},
+		transfer::SubstrateTransfer,
+	},
+};
 
 use selendra_config::CONFIG;
 
+// Splits names into words and indexes every lowercased prefix of each, for search_by_field
+const NAME_SEARCH_ANALYZER: &str =
+	"DEFINE ANALYZER name_search TOKENIZERS blank, class FILTERS lowercase, ascii, edgengram(1, 32);";
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SurrealDb {
 	pub surreal_db_url: String,
@@ -21,20 +43,8 @@ impl SurrealDb {
 	}
 
 	pub async fn setup_account_db(&self) -> GenericDB<SubstrateAccount> {
-		let name_space = &CONFIG.surreal_db.namespace;
-		let database = &CONFIG.surreal_db.database;
 		let table = &CONFIG.surreal_db.account_table;
-
-		let db = GenericDB::new(
-			&self.surreal_db_url,
-			&self.surreal_db_user,
-			&self.surreal_db_pass,
-			&name_space,
-			&database,
-			&table,
-		)
-		.await
-		.expect("Failed to create DB");
+		let db = self.connect(table).await;
 
 		// Define the schema with float type for balance fields
 		let schema = format!(
@@ -54,4 +64,685 @@ impl SurrealDb {
 
 		db
 	}
+
+	pub async fn setup_evm_account_db(&self) -> GenericDB<EvmAccount> {
+		let table = &CONFIG.surreal_db.evm_account_table;
+		let db = self.connect(table).await;
+
+		// Defaults let activity be recorded before the balance is first fetched
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD address ON {table} TYPE string;
+            DEFINE FIELD balance ON {table} TYPE string DEFAULT '0';
+            DEFINE FIELD nonce ON {table} TYPE int DEFAULT 0;
+            DEFINE FIELD is_contract ON {table} TYPE bool DEFAULT false;
+            DEFINE FIELD first_seen_block ON {table} TYPE int;
+            DEFINE FIELD last_seen_block ON {table} TYPE int;
+            DEFINE FIELD transaction_count ON {table} TYPE int DEFAULT 0;
+            DEFINE INDEX {table}_address ON {table} FIELDS address UNIQUE;
+            DEFINE INDEX {table}_last_seen ON {table} FIELDS last_seen_block;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_account_link_db(&self) -> GenericDB<AccountLink> {
+		let table = &CONFIG.surreal_db.account_link_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD evm_address ON {table} TYPE string;
+            DEFINE FIELD substrate_address ON {table} TYPE string;
+            DEFINE FIELD claimed ON {table} TYPE bool;
+            DEFINE INDEX {table}_evm ON {table} FIELDS evm_address UNIQUE;
+            DEFINE INDEX {table}_substrate ON {table} FIELDS substrate_address;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_identity_db(&self) -> GenericDB<Identity> {
+		let table = &CONFIG.surreal_db.identity_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD substrate_address ON {table} TYPE string;
+            DEFINE FIELD display_name ON {table} TYPE option<string>;
+            DEFINE FIELD legal_name ON {table} TYPE option<string>;
+            DEFINE FIELD web ON {table} TYPE option<string>;
+            DEFINE FIELD riot ON {table} TYPE option<string>;
+            DEFINE FIELD email ON {table} TYPE option<string>;
+            DEFINE FIELD twitter ON {table} TYPE option<string>;
+            DEFINE FIELD image ON {table} TYPE option<string>;
+            DEFINE FIELD judgement ON {table} TYPE option<string>;
+            DEFINE FIELD updated_at_block ON {table} TYPE int;
+            DEFINE INDEX {table}_address ON {table} FIELDS substrate_address UNIQUE;
+            DEFINE INDEX {table}_display_name ON {table} FIELDS display_name;
+            {NAME_SEARCH_ANALYZER}
+            DEFINE INDEX {table}_display_name_search ON {table} FIELDS display_name SEARCH ANALYZER name_search BM25;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_identity_history_db(&self) -> GenericDB<IdentityHistory> {
+		let table = &CONFIG.surreal_db.identity_history_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD substrate_address ON {table} TYPE string;
+            DEFINE FIELD event ON {table} TYPE string;
+            DEFINE FIELD registrar_index ON {table} TYPE option<int>;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD event_index ON {table} TYPE int;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE INDEX {table}_address ON {table} FIELDS substrate_address;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_session_db(&self) -> GenericDB<Session> {
+		let table = &CONFIG.surreal_db.session_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD session_index ON {table} TYPE int;
+            DEFINE FIELD era ON {table} TYPE int;
+            DEFINE FIELD start_block ON {table} TYPE int;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE FIELD validators ON {table} TYPE array<string>;
+            DEFINE FIELD queued_validators ON {table} TYPE array<string>;
+            DEFINE INDEX {table}_index ON {table} FIELDS session_index UNIQUE;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_session_validator_db(&self) -> GenericDB<SessionValidator> {
+		let table = &CONFIG.surreal_db.session_validator_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD session_index ON {table} TYPE int;
+            DEFINE FIELD validator ON {table} TYPE string;
+            DEFINE FIELD blocks_produced ON {table} TYPE int;
+            DEFINE INDEX {table}_session ON {table} FIELDS session_index;
+            DEFINE INDEX {table}_validator ON {table} FIELDS validator;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_era_validator_db(&self) -> GenericDB<EraValidator> {
+		let table = &CONFIG.surreal_db.era_validator_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD era ON {table} TYPE int;
+            DEFINE FIELD validator ON {table} TYPE string;
+            DEFINE FIELD blocks_produced ON {table} TYPE int;
+            DEFINE INDEX {table}_era ON {table} FIELDS era;
+            DEFINE INDEX {table}_validator ON {table} FIELDS validator;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_block_db(&self) -> GenericDB<SubstrateBlock> {
+		let table = &CONFIG.surreal_db.block_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD block_hash ON {table} TYPE string;
+            DEFINE FIELD parent_hash ON {table} TYPE string;
+            DEFINE FIELD extrinsics_root ON {table} TYPE string;
+            DEFINE FIELD state_root ON {table} TYPE string;
+            DEFINE FIELD author ON {table} TYPE option<string>;
+            DEFINE FIELD era ON {table} TYPE int;
+            DEFINE FIELD session_index ON {table} TYPE int;
+            DEFINE FIELD spec_name ON {table} TYPE string;
+            DEFINE FIELD spec_version ON {table} TYPE int;
+            DEFINE FIELD transaction_version ON {table} TYPE int;
+            DEFINE FIELD extrinsic_count ON {table} TYPE int;
+            DEFINE FIELD event_count ON {table} TYPE int;
+            DEFINE FIELD extrinsic_summary ON {table} TYPE array<object>;
+            DEFINE FIELD extrinsic_summary.*.pallet ON {table} TYPE string;
+            DEFINE FIELD extrinsic_summary.*.method ON {table} TYPE string;
+            DEFINE FIELD extrinsic_summary.*.count ON {table} TYPE int;
+            DEFINE FIELD event_summary ON {table} TYPE array<object>;
+            DEFINE FIELD event_summary.*.pallet ON {table} TYPE string;
+            DEFINE FIELD event_summary.*.method ON {table} TYPE string;
+            DEFINE FIELD event_summary.*.count ON {table} TYPE int;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE INDEX {table}_number ON {table} FIELDS block_number UNIQUE;
+            DEFINE INDEX {table}_hash ON {table} FIELDS block_hash UNIQUE;
+            DEFINE INDEX {table}_author ON {table} FIELDS author;
+            DEFINE INDEX {table}_era_author ON {table} FIELDS era, author;
+            DEFINE INDEX {table}_session_author ON {table} FIELDS session_index, author;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_extrinsic_db(&self) -> GenericDB<SubstrateExtrinsic> {
+		let table = &CONFIG.surreal_db.extrinsic_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD extrinsic_index ON {table} TYPE int;
+            DEFINE FIELD hash ON {table} TYPE string;
+            DEFINE FIELD pallet ON {table} TYPE string;
+            DEFINE FIELD method ON {table} TYPE string;
+            DEFINE FIELD call ON {table} TYPE string;
+            DEFINE FIELD signer ON {table} TYPE option<string>;
+            DEFINE FIELD fee ON {table} TYPE string;
+            DEFINE FIELD success ON {table} TYPE bool;
+            DEFINE FIELD error ON {table} TYPE option<string>;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE INDEX {table}_block ON {table} FIELDS block_number, extrinsic_index UNIQUE;
+            DEFINE INDEX {table}_hash ON {table} FIELDS hash;
+            DEFINE INDEX {table}_call ON {table} FIELDS pallet, method;
+            DEFINE INDEX {table}_signer ON {table} FIELDS signer;
+            DEFINE INDEX {table}_signer ON {table} FIELDS signer;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_event_db(&self) -> GenericDB<SubstrateEvent> {
+		let table = &CONFIG.surreal_db.event_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD event_index ON {table} TYPE int;
+            DEFINE FIELD extrinsic_index ON {table} TYPE option<int>;
+            DEFINE FIELD signer ON {table} TYPE option<string>;
+            DEFINE FIELD success ON {table} TYPE option<bool>;
+            DEFINE FIELD pallet ON {table} TYPE string;
+            DEFINE FIELD method ON {table} TYPE string;
+            DEFINE FIELD data ON {table} TYPE string;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE INDEX {table}_block ON {table} FIELDS block_number, event_index UNIQUE;
+            DEFINE INDEX {table}_extrinsic ON {table} FIELDS block_number, extrinsic_index;
+            DEFINE INDEX {table}_call ON {table} FIELDS pallet, method;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_transfer_db(&self) -> GenericDB<SubstrateTransfer> {
+		let table = &CONFIG.surreal_db.transfer_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD event_index ON {table} TYPE int;
+            DEFINE FIELD extrinsic_index ON {table} TYPE option<int>;
+            DEFINE FIELD from_address ON {table} TYPE string;
+            DEFINE FIELD to_address ON {table} TYPE string;
+            DEFINE FIELD amount ON {table} TYPE string;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE INDEX {table}_block ON {table} FIELDS block_number, event_index UNIQUE;
+            DEFINE INDEX {table}_from ON {table} FIELDS from_address;
+            DEFINE INDEX {table}_to ON {table} FIELDS to_address;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_offence_db(&self) -> GenericDB<Offence> {
+		let table = &CONFIG.surreal_db.offence_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD event ON {table} TYPE string;
+            DEFINE FIELD offender ON {table} TYPE string;
+            DEFINE FIELD reporter ON {table} TYPE option<string>;
+            DEFINE FIELD amount ON {table} TYPE option<string>;
+            DEFINE FIELD era ON {table} TYPE int;
+            DEFINE FIELD details ON {table} TYPE option<string>;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD event_index ON {table} TYPE int;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE INDEX {table}_offender ON {table} FIELDS offender;
+            DEFINE INDEX {table}_era ON {table} FIELDS era;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_daily_stats_db(&self) -> GenericDB<DailyStats> {
+		let table = &CONFIG.surreal_db.daily_stats_table;
+		let db = self.connect(table).await;
+
+		// Counters default to zero so each service only has to touch its own fields
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD date ON {table} TYPE string;
+            DEFINE FIELD blocks ON {table} TYPE int DEFAULT 0;
+            DEFINE FIELD extrinsics ON {table} TYPE int DEFAULT 0;
+            DEFINE FIELD transfers ON {table} TYPE int DEFAULT 0;
+            DEFINE FIELD active_accounts ON {table} TYPE int DEFAULT 0;
+            DEFINE FIELD new_accounts ON {table} TYPE int DEFAULT 0;
+            DEFINE FIELD fees_paid ON {table} TYPE string DEFAULT "0";
+            DEFINE FIELD evm_transactions ON {table} TYPE int DEFAULT 0;
+            DEFINE FIELD evm_gas_used ON {table} TYPE int DEFAULT 0;
+            DEFINE FIELD evm_fees_burned ON {table} TYPE string DEFAULT "0";
+            DEFINE INDEX {table}_date ON {table} FIELDS date UNIQUE;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_daily_account_db(&self) -> GenericDB<DailyAccount> {
+		let table = &CONFIG.surreal_db.daily_account_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD date ON {table} TYPE string;
+            DEFINE FIELD address ON {table} TYPE string;
+            DEFINE FIELD first_seen ON {table} TYPE string;
+            DEFINE INDEX {table}_date ON {table} FIELDS date;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_evm_block_db(&self) -> GenericDB<EvmBlockHeader> {
+		let table = &CONFIG.surreal_db.evm_block_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD block_hash ON {table} TYPE string;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE FIELD transaction_count ON {table} TYPE int;
+            DEFINE FIELD size ON {table} TYPE int;
+            DEFINE FIELD gas_used ON {table} TYPE int;
+            DEFINE FIELD gas_limit ON {table} TYPE int;
+            DEFINE FIELD base_fee_per_gas ON {table} TYPE string;
+            DEFINE FIELD burnt_fees ON {table} TYPE string;
+            DEFINE INDEX {table}_number ON {table} FIELDS block_number UNIQUE;
+            DEFINE INDEX {table}_hash ON {table} FIELDS block_hash UNIQUE;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_evm_transaction_db(&self) -> GenericDB<EvmTransaction> {
+		let table = &CONFIG.surreal_db.evm_transaction_table;
+		let db = self.connect(table).await;
+
+		// Wei amounts are uint256 and stored as decimal strings to stay lossless
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD transaction_hash ON {table} TYPE string;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE FIELD status ON {table} TYPE bool;
+            DEFINE FIELD from_address ON {table} TYPE string;
+            DEFINE FIELD to_address ON {table} TYPE string;
+            DEFINE FIELD is_contract ON {table} TYPE bool;
+            DEFINE FIELD contract_address ON {table} TYPE option<string>;
+            DEFINE FIELD value ON {table} TYPE string;
+            DEFINE FIELD gas_price ON {table} TYPE string;
+            DEFINE FIELD gas_used ON {table} TYPE int;
+            DEFINE FIELD fee ON {table} TYPE string;
+            DEFINE FIELD max_fee_per_gas ON {table} TYPE option<string>;
+            DEFINE FIELD max_priority_fee_per_gas ON {table} TYPE option<string>;
+            DEFINE FIELD effective_gas_price ON {table} TYPE string;
+            DEFINE FIELD burnt_fee ON {table} TYPE string;
+            DEFINE FIELD priority_fee ON {table} TYPE string;
+            DEFINE FIELD access_list ON {table} TYPE array<object>;
+            DEFINE FIELD access_list.*.address ON {table} TYPE string;
+            DEFINE FIELD access_list.*.storage_keys ON {table} TYPE array<string>;
+            DEFINE FIELD transaction_type ON {table} TYPE int;
+            DEFINE FIELD input ON {table} TYPE string;
+            DEFINE FIELD decoded_input ON {table} FLEXIBLE TYPE option<object>;
+            DEFINE FIELD revert_data ON {table} TYPE option<string>;
+            DEFINE FIELD error_message ON {table} TYPE option<string>;
+            DEFINE FIELD nonce ON {table} TYPE int;
+            DEFINE FIELD transaction_index ON {table} TYPE int;
+            DEFINE INDEX {table}_hash ON {table} FIELDS transaction_hash UNIQUE;
+            DEFINE INDEX {table}_block ON {table} FIELDS block_number;
+            DEFINE INDEX {table}_from ON {table} FIELDS from_address;
+            DEFINE INDEX {table}_to ON {table} FIELDS to_address;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_evm_log_db(&self) -> GenericDB<EvmLog> {
+		let table = &CONFIG.surreal_db.evm_log_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD transaction_hash ON {table} TYPE string;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD log_index ON {table} TYPE int;
+            DEFINE FIELD address ON {table} TYPE string;
+            DEFINE FIELD topics ON {table} TYPE array<string>;
+            DEFINE FIELD data ON {table} TYPE string;
+            DEFINE FIELD decoded ON {table} FLEXIBLE TYPE option<object>;
+            DEFINE INDEX {table}_transaction ON {table} FIELDS transaction_hash;
+            DEFINE INDEX {table}_address ON {table} FIELDS address;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_internal_transaction_db(&self) -> GenericDB<InternalTransaction> {
+		let table = &CONFIG.surreal_db.internal_transaction_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD transaction_hash ON {table} TYPE string;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE FIELD trace_index ON {table} TYPE int;
+            DEFINE FIELD call_type ON {table} TYPE string;
+            DEFINE FIELD from_address ON {table} TYPE string;
+            DEFINE FIELD to_address ON {table} TYPE string;
+            DEFINE FIELD value ON {table} TYPE string;
+            DEFINE FIELD depth ON {table} TYPE int;
+            DEFINE FIELD error ON {table} TYPE option<string>;
+            DEFINE INDEX {table}_transaction ON {table} FIELDS transaction_hash;
+            DEFINE INDEX {table}_from ON {table} FIELDS from_address;
+            DEFINE INDEX {table}_to ON {table} FIELDS to_address;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_token_transfer_db(&self) -> GenericDB<TokenTransfer> {
+		let table = &CONFIG.surreal_db.token_transfer_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD transaction_hash ON {table} TYPE string;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD log_index ON {table} TYPE int;
+            DEFINE FIELD token_address ON {table} TYPE string;
+            DEFINE FIELD event ON {table} TYPE string;
+            DEFINE FIELD from_address ON {table} TYPE string;
+            DEFINE FIELD to_address ON {table} TYPE string;
+            DEFINE FIELD value ON {table} TYPE string;
+            DEFINE FIELD token_id ON {table} TYPE option<string>;
+            DEFINE FIELD batch_index ON {table} TYPE option<int>;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE INDEX {table}_transaction ON {table} FIELDS transaction_hash;
+            DEFINE INDEX {table}_token ON {table} FIELDS token_address;
+            DEFINE INDEX {table}_from ON {table} FIELDS from_address;
+            DEFINE INDEX {table}_to ON {table} FIELDS to_address;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	async fn connect<T>(&self, table: &str) -> GenericDB<T>
+	where
+		T: Serialize + DeserializeOwned + Clone + 'static,
+	{
+		GenericDB::new(
+			&self.surreal_db_url,
+			&self.surreal_db_user,
+			&self.surreal_db_pass,
+			&CONFIG.surreal_db.namespace,
+			&CONFIG.surreal_db.database,
+			table,
+		)
+		.await
+		.expect("Failed to create DB")
+	}
+
+	pub async fn setup_token_db(&self) -> GenericDB<Token> {
+		let table = &CONFIG.surreal_db.token_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD address ON {table} TYPE string;
+            DEFINE FIELD name ON {table} TYPE string;
+            DEFINE FIELD symbol ON {table} TYPE string;
+            DEFINE FIELD decimals ON {table} TYPE int;
+            DEFINE FIELD total_supply ON {table} TYPE string;
+            DEFINE FIELD holder_count ON {table} TYPE int DEFAULT 0;
+            DEFINE INDEX {table}_address ON {table} FIELDS address UNIQUE;
+            DEFINE INDEX {table}_symbol ON {table} FIELDS symbol;
+            {NAME_SEARCH_ANALYZER}
+            DEFINE INDEX {table}_symbol_search ON {table} FIELDS symbol SEARCH ANALYZER name_search BM25;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_token_holder_db(&self) -> GenericDB<TokenHolder> {
+		let table = &CONFIG.surreal_db.token_holder_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD token_address ON {table} TYPE string;
+            DEFINE FIELD holder ON {table} TYPE string;
+            DEFINE FIELD balance ON {table} TYPE string;
+            DEFINE FIELD last_updated_block ON {table} TYPE int;
+            DEFINE INDEX {table}_token_holder ON {table} FIELDS token_address, holder UNIQUE;
+            DEFINE INDEX {table}_holder ON {table} FIELDS holder;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_contract_db(&self) -> GenericDB<Contract> {
+		let table = &CONFIG.surreal_db.contract_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD address ON {table} TYPE string;
+            DEFINE FIELD creator ON {table} TYPE string;
+            DEFINE FIELD creation_transaction ON {table} TYPE string;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            DEFINE FIELD bytecode_hash ON {table} TYPE string;
+            DEFINE FIELD contract_type ON {table} TYPE string;
+            DEFINE FIELD timestamp ON {table} TYPE int;
+            DEFINE INDEX {table}_address ON {table} FIELDS address UNIQUE;
+            DEFINE INDEX {table}_creator ON {table} FIELDS creator;
+            DEFINE INDEX {table}_bytecode ON {table} FIELDS bytecode_hash;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_contract_abi_db(&self) -> GenericDB<ContractAbi> {
+		let table = &CONFIG.surreal_db.contract_abi_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD address ON {table} TYPE string;
+            DEFINE FIELD name ON {table} TYPE option<string>;
+            DEFINE FIELD abi ON {table} TYPE string;
+            DEFINE INDEX {table}_address ON {table} FIELDS address UNIQUE;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_nft_token_db(&self) -> GenericDB<NftToken> {
+		let table = &CONFIG.surreal_db.nft_token_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD contract_address ON {table} TYPE string;
+            DEFINE FIELD token_id ON {table} TYPE string;
+            DEFINE FIELD owner ON {table} TYPE string;
+            DEFINE FIELD mint_block ON {table} TYPE option<int>;
+            DEFINE FIELD last_transfer_block ON {table} TYPE int;
+            DEFINE FIELD token_uri ON {table} TYPE option<string>;
+            DEFINE FIELD metadata ON {table} FLEXIBLE TYPE option<object>;
+            DEFINE INDEX {table}_token ON {table} FIELDS contract_address, token_id UNIQUE;
+            DEFINE INDEX {table}_owner ON {table} FIELDS owner;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_nft_balance_db(&self) -> GenericDB<NftBalance> {
+		let table = &CONFIG.surreal_db.nft_balance_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD contract_address ON {table} TYPE string;
+            DEFINE FIELD token_id ON {table} TYPE string;
+            DEFINE FIELD owner ON {table} TYPE string;
+            DEFINE FIELD balance ON {table} TYPE string;
+            DEFINE FIELD last_transfer_block ON {table} TYPE int;
+            DEFINE FIELD token_uri ON {table} TYPE option<string>;
+            DEFINE FIELD metadata ON {table} FLEXIBLE TYPE option<object>;
+            DEFINE INDEX {table}_token_owner ON {table} FIELDS contract_address, token_id, owner UNIQUE;
+            DEFINE INDEX {table}_owner ON {table} FIELDS owner;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
+
+	pub async fn setup_index_cursor_db(&self) -> GenericDB<IndexCursor> {
+		let table = &CONFIG.surreal_db.index_cursor_table;
+		let db = self.connect(table).await;
+
+		let schema = format!(
+			r#"
+            DEFINE TABLE {table} SCHEMAFULL;
+            DEFINE FIELD name ON {table} TYPE string;
+            DEFINE FIELD block_number ON {table} TYPE int;
+            "#
+		);
+
+		db.db.query(schema).await.expect("Failed to define schema");
+
+		db
+	}
 }
@@ -0,0 +1,177 @@
+use anyhow::{anyhow, Result};
+use std::{fmt, str::FromStr};
+
+use ethers::{types::H160, utils::to_checksum};
+use sp_core::{
+	blake2_256,
+	crypto::{Ss58AddressFormat, Ss58Codec},
+};
+use sp_runtime::AccountId32;
+
+/// SS58 prefix registered for Selendra, used for every address this crate outputs
+pub const SELENDRA_SS58_PREFIX: u16 = 204;
+
+/// An address of either side of the chain, detected from user input.
+///
+/// Accepts SS58 with any network prefix, a hex encoded 32-byte account and a 20-byte EVM
+/// address, whose EIP-55 checksum is verified when it is given in mixed case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainAddress {
+	Substrate(AccountId32),
+	Evm(H160),
+}
+
+impl ChainAddress {
+	pub fn parse(input: &str) -> Result<Self> {
+		let input = input.trim();
+		match input.strip_prefix("0x") {
+			Some(hex_body) if hex_body.len() == 40 => parse_evm_address(input).map(Self::Evm),
+			Some(hex_body) if hex_body.len() == 64 => {
+				let bytes: [u8; 32] = hex::decode(hex_body)
+					.map_err(|e| anyhow!("Invalid hex account {}: {:?}", input, e))?
+					.try_into()
+					.map_err(|_| anyhow!("Invalid hex account {}", input))?;
+				Ok(Self::Substrate(AccountId32::from(bytes)))
+			},
+			Some(_) => Err(anyhow!("Hex address must be 20 or 32 bytes: {}", input)),
+			None => AccountId32::from_ss58check_with_version(input)
+				.map(|(account, _)| Self::Substrate(account))
+				.map_err(|e| anyhow!("Invalid SS58 address {}: {:?}", input, e)),
+		}
+	}
+
+	pub fn is_evm(&self) -> bool {
+		matches!(self, Self::Evm(_))
+	}
+
+	/// Form addresses are stored in: Selendra SS58 for accounts, lowercase hex for EVM
+	pub fn canonical(&self) -> String {
+		match self {
+			Self::Substrate(account) => encode_ss58(account),
+			Self::Evm(address) => format!("0x{}", hex::encode(address.as_bytes())),
+		}
+	}
+}
+
+impl FromStr for ChainAddress {
+	type Err = anyhow::Error;
+
+	fn from_str(input: &str) -> Result<Self> {
+		Self::parse(input)
+	}
+}
+
+impl fmt::Display for ChainAddress {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.canonical())
+	}
+}
+
+/// Encode an account with Selendra's SS58 prefix
+pub fn encode_ss58<T: Ss58Codec>(account: &T) -> String {
+	account.to_ss58check_with_version(Ss58AddressFormat::custom(SELENDRA_SS58_PREFIX))
+}
+
+/// Re-encode an SS58 address of any network with Selendra's prefix
+pub fn normalize_ss58_address(address: &str) -> Option<String> {
+	AccountId32::from_ss58check_with_version(address.trim())
+		.ok()
+		.map(|(account, _)| encode_ss58(&account))
+}
+
+/// Parse a `0x` prefixed EVM address; mixed case input must carry a valid EIP-55 checksum
+pub fn parse_evm_address(address: &str) -> Result<H160> {
+	let hex_body = address
+		.strip_prefix("0x")
+		.filter(|hex_body| hex_body.len() == 40)
+		.ok_or_else(|| anyhow!("EVM address must be 0x followed by 40 hex digits: {}", address))?;
+	let parsed = H160::from_str(hex_body).map_err(|e| anyhow!("Invalid EVM address: {:?}", e))?;
+
+	let has_lower = hex_body.chars().any(|c| c.is_ascii_lowercase());
+	let has_upper = hex_body.chars().any(|c| c.is_ascii_uppercase());
+	if has_lower && has_upper && to_checksum(&parsed, None) != address {
+		return Err(anyhow!("Invalid EIP-55 checksum: {}", address));
+	}
+	Ok(parsed)
+}
+
+/// Lowercase hex form of a valid EVM address
+pub fn normalize_evm_address(address: &str) -> Option<String> {
+	parse_evm_address(address.trim())
+		.ok()
+		.map(|address| ChainAddress::Evm(address).canonical())
+}
+
+/// EIP-55 checksummed form of an EVM address, for display
+pub fn to_checksum_address(address: &H160) -> String {
+	to_checksum(address, None)
+}
+
+pub fn validate_ss58_address(address: &str) -> bool {
+	normalize_ss58_address(address).is_some()
+}
+
+pub fn validate_evm_address(address: &str) -> bool {
+	parse_evm_address(address).is_ok()
+}
+
+/// Substrate account the runtime's `HashedAddressMapping<BlakeTwo256>` derives for an EVM
+/// address: `blake2_256("evm:" ++ address)`
+pub fn evm_to_substrate_address(evm_address: &str) -> Option<String> {
+	let address = parse_evm_address(evm_address).ok()?;
+	let mut data = [0u8; 24];
+	data[0..4].copy_from_slice(b"evm:");
+	data[4..24].copy_from_slice(address.as_bytes());
+
+	Some(encode_ss58(&AccountId32::from(blake2_256(&data))))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+	const ALICE_HEX: &str = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+	#[test]
+	fn test_parse_detects_address_kind() {
+		let from_ss58 = ChainAddress::parse(ALICE).unwrap();
+		let from_hex = ChainAddress::parse(ALICE_HEX).unwrap();
+		assert_eq!(from_ss58, from_hex);
+		assert!(!from_ss58.is_evm());
+		assert_eq!(normalize_ss58_address(&from_ss58.canonical()), Some(from_ss58.canonical()));
+
+		let evm = ChainAddress::parse("0x52908400098527886E0F7030069857D2E4169EE7").unwrap();
+		assert!(evm.is_evm());
+		assert_eq!(evm.canonical(), "0x52908400098527886e0f7030069857d2e4169ee7");
+
+		assert!(ChainAddress::parse("0x1234").is_err());
+		assert!(ChainAddress::parse("not an address").is_err());
+	}
+
+	#[test]
+	fn test_evm_checksum() {
+		// EIP-55 test vector
+		assert!(validate_evm_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+		assert!(validate_evm_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"));
+		assert!(!validate_evm_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"));
+		assert_eq!(
+			to_checksum_address(
+				&parse_evm_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap()
+			),
+			"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+		);
+	}
+
+	#[test]
+	fn test_evm_to_substrate_address() {
+		let address = evm_to_substrate_address("0x1000000000000000000000000000000000000001")
+			.expect("valid address");
+		let (account, _) = AccountId32::from_ss58check_with_version(&address).unwrap();
+		assert_eq!(
+			hex::encode(<AccountId32 as AsRef<[u8]>>::as_ref(&account)),
+			"0e140211b5dca00bc5ac64b80bfec07ecc45022e5c9010a53090e770322a66f9"
+		);
+		assert_eq!(evm_to_substrate_address("not an address"), None);
+	}
+}
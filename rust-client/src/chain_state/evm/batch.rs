@@ -0,0 +1,82 @@
+use anyhow::{anyhow, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize)]
+struct BatchRequest<'a> {
+	jsonrpc: &'a str,
+	id: usize,
+	method: &'a str,
+	params: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchResponse {
+	id: usize,
+	result: Option<Value>,
+	error: Option<Value>,
+}
+
+/// Sends many calls of one JSON-RPC method in a single HTTP round trip, which the ethers
+/// HTTP transport does not support.
+#[derive(Debug, Clone)]
+pub struct BatchClient {
+	http: reqwest::Client,
+	url: String,
+}
+
+impl BatchClient {
+	pub fn new(url: &str) -> Self {
+		Self { http: reqwest::Client::new(), url: url.to_string() }
+	}
+
+	/// Results are returned in the same order as `params`.
+	pub async fn request<T: DeserializeOwned>(
+		&self,
+		method: &str,
+		params: Vec<Value>,
+	) -> Result<Vec<T>> {
+		if params.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let requests: Vec<BatchRequest> = params
+			.into_iter()
+			.enumerate()
+			.map(|(id, params)| BatchRequest { jsonrpc: "2.0", id, method, params })
+			.collect();
+
+		let mut responses: Vec<BatchResponse> = self
+			.http
+			.post(&self.url)
+			.json(&requests)
+			.send()
+			.await
+			.map_err(|e| anyhow!("Batch {} request failed: {:?}", method, e))?
+			.json()
+			.await
+			.map_err(|e| anyhow!("Invalid batch {} response: {:?}", method, e))?;
+
+		if responses.len() != requests.len() {
+			return Err(anyhow!(
+				"Batch {} returned {} responses for {} requests",
+				method,
+				responses.len(),
+				requests.len()
+			));
+		}
+
+		// Servers may answer a batch in any order
+		responses.sort_by_key(|response| response.id);
+		responses
+			.into_iter()
+			.map(|response| match (response.result, response.error) {
+				(_, Some(error)) => Err(anyhow!("Batch {} error: {}", method, error)),
+				(Some(result), None) => serde_json::from_value(result)
+					.map_err(|e| anyhow!("Error decoding {} result: {:?}", method, e)),
+				(None, None) => serde_json::from_value(Value::Null)
+					.map_err(|e| anyhow!("Error decoding {} result: {:?}", method, e)),
+			})
+			.collect()
+	}
+}
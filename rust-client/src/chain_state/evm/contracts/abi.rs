@@ -82,3 +82,29 @@ pub const ERC20_ABI: &str = r#"[
         "type": "function"
     }
 ]"#;
+
+// ERC721 metadata extension
+pub const ERC721_ABI: &str = r#"[
+    {
+        "constant": true,
+        "inputs": [{"name": "_tokenId", "type": "uint256"}],
+        "name": "tokenURI",
+        "outputs": [{"name": "", "type": "string"}],
+        "payable": false,
+        "stateMutability": "view",
+        "type": "function"
+    }
+]"#;
+
+// ERC1155 metadata URI extension
+pub const ERC1155_ABI: &str = r#"[
+    {
+        "constant": true,
+        "inputs": [{"name": "_id", "type": "uint256"}],
+        "name": "uri",
+        "outputs": [{"name": "", "type": "string"}],
+        "payable": false,
+        "stateMutability": "view",
+        "type": "function"
+    }
+]"#;
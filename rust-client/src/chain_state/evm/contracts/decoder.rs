@@ -0,0 +1,393 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::{collections::HashMap, str::FromStr};
+
+use ethers::{
+	abi::{decode, parse_abi, Abi, AbiError, Event, Function, ParamType, RawLog, Token},
+	types::{H160, H256, I256},
+};
+
+use super::signatures::{EVENT_SIGNATURES, FUNCTION_SIGNATURES};
+use crate::models::{
+	contract::{DecodedData, DecodedParam},
+	extrinsic::{EvmLog, TransactionDetail},
+};
+
+// `Error(string)`, raised by `require` and `revert` with a message
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+// `Panic(uint256)`, raised by failing asserts and checked arithmetic
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decodes transaction input and logs into named, typed parameters.
+///
+/// ABIs registered for an address take precedence; anything else is matched against the
+/// bundled signature database by selector or topic.
+#[derive(Debug, Clone)]
+pub struct AbiDecoder {
+	contracts: HashMap<H160, Abi>,
+	functions: HashMap<[u8; 4], Vec<Function>>,
+	events: HashMap<H256, Vec<Event>>,
+}
+
+impl Default for AbiDecoder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl AbiDecoder {
+	pub fn new() -> Self {
+		let bundled = parse_abi(&[FUNCTION_SIGNATURES, EVENT_SIGNATURES].concat())
+			.expect("bundled signatures are valid");
+
+		let mut functions: HashMap<[u8; 4], Vec<Function>> = HashMap::new();
+		for function in bundled.functions() {
+			functions.entry(function.short_signature()).or_default().push(function.clone());
+		}
+		let mut events: HashMap<H256, Vec<Event>> = HashMap::new();
+		for event in bundled.events() {
+			events.entry(event.signature()).or_default().push(event.clone());
+		}
+
+		Self { contracts: HashMap::new(), functions, events }
+	}
+
+	/// Register the JSON ABI of a contract, replacing any earlier one
+	pub fn register_abi(&mut self, address: &str, abi_json: &str) -> Result<()> {
+		let address = H160::from_str(address)?;
+		self.contracts.insert(address, parse_abi_json(abi_json)?);
+		Ok(())
+	}
+
+	/// Whether an ABI is registered for `address`
+	pub fn is_registered(&self, address: &str) -> bool {
+		H160::from_str(address).map_or(false, |address| self.contracts.contains_key(&address))
+	}
+
+	/// Decode the input of a call to `to`, `None` when no known function matches
+	pub fn decode_input(&self, to: &str, input: &[u8]) -> Option<DecodedData> {
+		let selector: [u8; 4] = input.get(0..4)?.try_into().ok()?;
+		let data = &input[4..];
+
+		let registered = H160::from_str(to)
+			.ok()
+			.and_then(|address| self.contracts.get(&address))
+			.into_iter()
+			.flat_map(|abi| abi.functions())
+			.filter(|function| function.short_signature() == selector);
+		let bundled = self.functions.get(&selector).into_iter().flatten();
+
+		registered.chain(bundled).find_map(|function| {
+			let tokens = function.decode_input(data).ok()?;
+			Some(DecodedData {
+				name: function.name.clone(),
+				signature: signature(
+					&function.name,
+					function.inputs.iter().map(|input| &input.kind),
+				),
+				params: function
+					.inputs
+					.iter()
+					.zip(tokens)
+					.enumerate()
+					.map(|(index, (param, token))| DecodedParam {
+						name: param_name(&param.name, index),
+						kind: param.kind.to_string(),
+						value: token_to_json(&token),
+					})
+					.collect(),
+			})
+		})
+	}
+
+	/// Decode a log emitted by `address`, `None` when no known event matches
+	pub fn decode_log(&self, address: &str, topics: &[H256], data: &[u8]) -> Option<DecodedData> {
+		let topic = topics.first()?;
+
+		let registered = H160::from_str(address)
+			.ok()
+			.and_then(|address| self.contracts.get(&address))
+			.into_iter()
+			.flat_map(|abi| abi.events())
+			.filter(|event| !event.anonymous && event.signature() == *topic);
+		let bundled = self.events.get(topic).into_iter().flatten();
+
+		// Candidates sharing a topic differ in which params are indexed, so the wrong ones fail
+		registered.chain(bundled).find_map(|event| {
+			let log =
+				event.parse_log(RawLog { topics: topics.to_vec(), data: data.to_vec() }).ok()?;
+			Some(DecodedData {
+				name: event.name.clone(),
+				signature: signature(&event.name, event.inputs.iter().map(|input| &input.kind)),
+				params: event
+					.inputs
+					.iter()
+					.zip(log.params)
+					.enumerate()
+					.map(|(index, (input, param))| DecodedParam {
+						name: param_name(&input.name, index),
+						kind: input.kind.to_string(),
+						value: token_to_json(&param.value),
+					})
+					.collect(),
+			})
+		})
+	}
+
+	/// Decode the call of an indexed transaction, `None` for transfers and contract creations
+	pub fn decode_transaction(&self, transaction: &TransactionDetail) -> Option<DecodedData> {
+		if transaction.to.is_empty() {
+			return None;
+		}
+		let input = hex::decode(transaction.input.trim_start_matches("0x")).ok()?;
+		self.decode_input(&transaction.to, &input)
+	}
+
+	/// Revert reason of an indexed transaction, `None` for successful ones
+	pub fn decode_transaction_revert(&self, transaction: &TransactionDetail) -> Option<String> {
+		if transaction.status {
+			return None;
+		}
+		transaction
+			.revert_data
+			.as_ref()
+			.and_then(|data| hex::decode(data.trim_start_matches("0x")).ok())
+			.and_then(|data| self.decode_revert(&transaction.to, &data))
+			.or(Some("Transaction Failed".to_string()))
+	}
+
+	/// Decode an indexed log with its hex encoded topics and data
+	pub fn decode_evm_log(&self, log: &EvmLog) -> Option<DecodedData> {
+		let topics: Vec<H256> = log
+			.topics
+			.iter()
+			.map(|topic| H256::from_str(topic))
+			.collect::<Result<_, _>>()
+			.ok()?;
+		let data = hex::decode(log.data.trim_start_matches("0x")).ok()?;
+		self.decode_log(&log.address, &topics, &data)
+	}
+
+	/// Human readable revert reason from the revert data of a call to `to`.
+	///
+	/// Covers `Error(string)`, `Panic(uint256)` and custom errors of registered ABIs, preferring
+	/// the ABI of `to`. Empty revert data yields `None`.
+	pub fn decode_revert(&self, to: &str, data: &[u8]) -> Option<String> {
+		let selector: [u8; 4] = data.get(0..4)?.try_into().ok()?;
+		let args = &data[4..];
+
+		if selector == ERROR_SELECTOR {
+			if let Some(Token::String(message)) = decode(&[ParamType::String], args)
+				.ok()
+				.and_then(|tokens| tokens.into_iter().next())
+			{
+				return Some(message);
+			}
+		}
+		if selector == PANIC_SELECTOR {
+			if let Some(Token::Uint(code)) = decode(&[ParamType::Uint(256)], args)
+				.ok()
+				.and_then(|tokens| tokens.into_iter().next())
+			{
+				return Some(format!("Panic(0x{:02x}): {}", code, panic_reason(code.low_u64())));
+			}
+		}
+
+		let to = H160::from_str(to).ok();
+		let target = to.and_then(|address| self.contracts.get(&address));
+		let others = self
+			.contracts
+			.iter()
+			.filter(|(address, _)| Some(**address) != to)
+			.map(|(_, abi)| abi);
+		target
+			.into_iter()
+			.chain(others)
+			.flat_map(|abi| abi.errors())
+			.filter(|error| error.signature()[0..4] == selector)
+			.find_map(|error| format_custom_error(error, args))
+			.or_else(|| Some(format!("Reverted with 0x{}", hex::encode(data))))
+	}
+
+	/// Canonical signature of a function selector, e.g. `transfer(address,uint256)`
+	pub fn function_signature(&self, selector: &[u8; 4]) -> Option<String> {
+		self.functions
+			.get(selector)
+			.and_then(|functions| functions.first())
+			.map(|function| {
+				signature(&function.name, function.inputs.iter().map(|input| &input.kind))
+			})
+	}
+}
+
+/// Parse a JSON ABI, accepting either the bare array or a compiler artifact with an `abi` key
+pub fn parse_abi_json(abi_json: &str) -> Result<Abi> {
+	let value: Value = serde_json::from_str(abi_json)?;
+	let abi = match value {
+		Value::Object(mut artifact) =>
+			artifact.remove("abi").ok_or_else(|| anyhow!("Artifact has no abi field"))?,
+		abi => abi,
+	};
+	serde_json::from_value(abi).map_err(|e| anyhow!("Invalid ABI: {:?}", e))
+}
+
+fn format_custom_error(error: &AbiError, args: &[u8]) -> Option<String> {
+	let tokens = error.decode(args).ok()?;
+	let params: Vec<String> = error
+		.inputs
+		.iter()
+		.zip(tokens)
+		.enumerate()
+		.map(|(index, (input, token))| {
+			let value = match token_to_json(&token) {
+				Value::String(value) => value,
+				value => value.to_string(),
+			};
+			format!("{}={}", param_name(&input.name, index), value)
+		})
+		.collect();
+	Some(format!("{}({})", error.name, params.join(", ")))
+}
+
+/// Meaning of the Solidity panic codes
+fn panic_reason(code: u64) -> &'static str {
+	match code {
+		0x01 => "assertion failed",
+		0x11 => "arithmetic overflow or underflow",
+		0x12 => "division or modulo by zero",
+		0x21 => "invalid enum value",
+		0x22 => "invalid storage byte array encoding",
+		0x31 => "pop on empty array",
+		0x32 => "array index out of bounds",
+		0x41 => "out of memory",
+		0x51 => "call to uninitialized internal function",
+		_ => "generic panic",
+	}
+}
+
+fn signature<'a>(name: &str, kinds: impl Iterator<Item = &'a ParamType>) -> String {
+	let kinds: Vec<String> = kinds.map(|kind| kind.to_string()).collect();
+	format!("{}({})", name, kinds.join(","))
+}
+
+fn param_name(name: &str, index: usize) -> String {
+	if name.is_empty() {
+		format!("arg{}", index)
+	} else {
+		name.to_string()
+	}
+}
+
+/// Integers are decimal strings so uint256 values survive JSON clients
+fn token_to_json(token: &Token) -> Value {
+	match token {
+		Token::Address(address) => json!(format!("0x{}", hex::encode(address.as_bytes()))),
+		Token::Uint(value) => json!(value.to_string()),
+		Token::Int(value) => json!(I256::from_raw(*value).to_string()),
+		Token::Bool(value) => json!(value),
+		Token::String(value) => json!(value),
+		Token::Bytes(bytes) | Token::FixedBytes(bytes) =>
+			json!(format!("0x{}", hex::encode(bytes))),
+		Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) =>
+			Value::Array(tokens.iter().map(token_to_json).collect()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethers::{abi::encode, types::U256, utils::keccak256};
+
+	const TOKEN: &str = "0x00000000000000000000000000000000000000aa";
+
+	#[test]
+	fn test_decode_bundled_transfer() {
+		let decoder = AbiDecoder::new();
+		let mut input = keccak256("transfer(address,uint256)")[0..4].to_vec();
+		input.extend(encode(&[Token::Address(H160::repeat_byte(0x11)), Token::Uint(U256::MAX)]));
+
+		let decoded = decoder.decode_input(TOKEN, &input).expect("transfer should decode");
+		assert_eq!(decoded.name, "transfer");
+		assert_eq!(decoded.signature, "transfer(address,uint256)");
+		assert_eq!(decoded.params[0].name, "to");
+		assert_eq!(decoded.params[0].value, json!(format!("0x{}", "11".repeat(20))));
+		assert_eq!(decoded.params[1].kind, "uint256");
+		assert_eq!(decoded.params[1].value, json!(U256::MAX.to_string()));
+	}
+
+	#[test]
+	fn test_decode_registered_abi() {
+		let mut decoder = AbiDecoder::new();
+		let abi = r#"[{"type":"function","name":"setScore","inputs":[{"name":"player","type":"address"},{"name":"score","type":"int256"}],"outputs":[],"stateMutability":"nonpayable"}]"#;
+		assert!(!decoder.is_registered(TOKEN));
+		decoder.register_abi(TOKEN, abi).unwrap();
+		assert!(decoder.is_registered(TOKEN));
+
+		let mut input = keccak256("setScore(address,int256)")[0..4].to_vec();
+		input.extend(encode(&[
+			Token::Address(H160::zero()),
+			Token::Int(I256::from(-5i64).into_raw()),
+		]));
+
+		let decoded = decoder.decode_input(TOKEN, &input).expect("registered call should decode");
+		assert_eq!(decoded.name, "setScore");
+		assert_eq!(decoded.params[1].value, json!("-5"));
+
+		// Unknown to other contracts
+		let other = "0x00000000000000000000000000000000000000bb";
+		assert!(decoder.decode_input(other, &input).is_none());
+	}
+
+	#[test]
+	fn test_decode_log_picks_matching_indexed_layout() {
+		let decoder = AbiDecoder::new();
+		let topic = H256::from(keccak256("Transfer(address,address,uint256)"));
+		let from = H256::from(H160::repeat_byte(0x11));
+		let to = H256::from(H160::repeat_byte(0x22));
+
+		let erc20 = decoder
+			.decode_log(TOKEN, &[topic, from, to], &encode(&[Token::Uint(U256::from(7))]))
+			.expect("erc20 transfer should decode");
+		assert_eq!(erc20.params[2].name, "value");
+
+		let erc721 = decoder
+			.decode_log(TOKEN, &[topic, from, to, H256::from_low_u64_be(9)], &[])
+			.expect("erc721 transfer should decode");
+		assert_eq!(erc721.params[2].name, "tokenId");
+		assert_eq!(erc721.params[2].value, json!("9"));
+	}
+
+	#[test]
+	fn test_decode_revert() {
+		let mut decoder = AbiDecoder::new();
+
+		let mut error = ERROR_SELECTOR.to_vec();
+		error.extend(encode(&[Token::String("insufficient balance".to_string())]));
+		assert_eq!(decoder.decode_revert(TOKEN, &error).as_deref(), Some("insufficient balance"));
+
+		let mut panic = PANIC_SELECTOR.to_vec();
+		panic.extend(encode(&[Token::Uint(U256::from(0x11))]));
+		assert_eq!(
+			decoder.decode_revert(TOKEN, &panic).as_deref(),
+			Some("Panic(0x11): arithmetic overflow or underflow")
+		);
+
+		let abi = r#"[{"type":"error","name":"InsufficientBalance","inputs":[{"name":"available","type":"uint256"},{"name":"required","type":"uint256"}]}]"#;
+		decoder.register_abi(TOKEN, abi).unwrap();
+		let mut custom = keccak256("InsufficientBalance(uint256,uint256)")[0..4].to_vec();
+		custom.extend(encode(&[Token::Uint(U256::from(1)), Token::Uint(U256::from(2))]));
+		assert_eq!(
+			decoder.decode_revert(TOKEN, &custom).as_deref(),
+			Some("InsufficientBalance(available=1, required=2)")
+		);
+
+		assert!(decoder.decode_revert(TOKEN, &[]).is_none());
+	}
+
+	#[test]
+	fn test_parse_abi_json_artifact() {
+		let artifact = r#"{"contractName":"Empty","abi":[]}"#;
+		assert!(parse_abi_json(artifact).is_ok());
+		assert!(parse_abi_json("not json").is_err());
+	}
+}
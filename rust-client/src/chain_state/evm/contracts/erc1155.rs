@@ -0,0 +1,58 @@
+use anyhow::Result;
+use std::sync::Arc;
+
+use ethers::{
+	contract::Contract,
+	providers::{Http, Provider},
+	types::{Address, U256},
+};
+
+use super::abi::ERC1155_ABI;
+
+#[derive(Debug, Clone)]
+pub struct ERC1155MetadataFetcher {
+	pub provider: Provider<Http>,
+}
+
+impl ERC1155MetadataFetcher {
+	pub fn new(provider: Provider<Http>) -> Self {
+		Self { provider }
+	}
+
+	/// Metadata URI of a token id, `None` when the contract does not implement `uri(uint256)`
+	pub async fn get_token_uri(
+		&self,
+		token_address: Address,
+		token_id: U256,
+	) -> Result<Option<String>> {
+		let abi: ethers::abi::Abi = serde_json::from_str(ERC1155_ABI)?;
+		let contract = Contract::new(token_address, abi, Arc::new(self.provider.clone()));
+
+		match contract.method::<_, String>("uri", token_id)?.call().await {
+			Ok(uri) => Ok(Some(substitute_token_id(&uri, token_id))),
+			Err(_) => Ok(None),
+		}
+	}
+}
+
+/// Clients replace `{id}` with the token id as 64 lowercase hex digits, per EIP-1155
+fn substitute_token_id(uri: &str, token_id: U256) -> String {
+	uri.replace("{id}", &format!("{:064x}", token_id))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_substitute_token_id() {
+		assert_eq!(
+			substitute_token_id("https://token-cdn/{id}.json", U256::from(314_592)),
+			"https://token-cdn/000000000000000000000000000000000000000000000000000000000004cce0.json"
+		);
+		assert_eq!(
+			substitute_token_id("ipfs://metadata.json", U256::one()),
+			"ipfs://metadata.json"
+		);
+	}
+}
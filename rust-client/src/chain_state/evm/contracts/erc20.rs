@@ -1,55 +1,43 @@
 use anyhow::{anyhow, Ok, Result};
+use once_cell::sync::Lazy;
+use serde_json::Value;
 use sp_core::H256;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+	collections::HashMap,
+	sync::{Arc, RwLock},
+};
 
 use ethers::{
+	abi::Abi,
 	contract::Contract,
 	providers::{Http, Middleware, Provider},
-	types::{Address, TransactionReceipt, U256},
+	types::{Address, Transaction, TransactionReceipt, U256},
 };
 
-use crate::models::{
-	contract::{ERC20Transaction, Erc20Info},
-	gas::TransactionGas,
+use crate::{
+	chain_state::evm::revert::RevertReasonFetcher,
+	models::{
+		contract::{ERC20Transaction, Erc20Info},
+		gas::TransactionGas,
+	},
 };
 
-use super::abi::ERC20_ABI;
+use super::{abi::ERC20_ABI, decoder::AbiDecoder};
+
+// Parsed once instead of on every token lookup
+static ERC20_CONTRACT_ABI: Lazy<Abi> =
+	Lazy::new(|| serde_json::from_str(ERC20_ABI).expect("ERC20_ABI is valid JSON"));
 
 #[derive(Debug, Clone)]
 pub struct ERC20TransactionFetcher {
 	pub provider: Provider<Http>,
-	pub function_signatures: HashMap<[u8; 4], String>,
+	pub decoder: Arc<RwLock<AbiDecoder>>,
+	revert_fetcher: RevertReasonFetcher,
 }
 
 impl ERC20TransactionFetcher {
-	pub fn new(provider: Provider<Http>) -> Self {
-		let function_signatures = HashMap::from([
-			([0xa9, 0x05, 0x9c, 0xbb], "transfer(address,uint256)".to_string()),
-			([0x09, 0x5e, 0xa7, 0xb3], "approve(address,uint256)".to_string()),
-			([0x23, 0xb8, 0x72, 0xdd], "transferFrom(address,address,uint256)".to_string()),
-			([0x70, 0xa0, 0x82, 0x31], "balanceOf(address)".to_string()),
-			([0xdd, 0x62, 0xed, 0x3e], "allowance(address,address)".to_string()),
-			([0x18, 0x16, 0x0d, 0xdd], "totalSupply()".to_string()),
-			([0x40, 0xc1, 0x0f, 0x19], "mint(address,uint256)".to_string()),
-			([0x42, 0x96, 0x66, 0x96], "burn(uint256)".to_string()),
-			([0x79, 0xcc, 0x67, 0x90], "burnFrom(address,uint256)".to_string()),
-			([0x39, 0x50, 0x93, 0x51], "increaseAllowance(address,uint256)".to_string()),
-			([0xa4, 0x57, 0xc2, 0xd7], "decreaseAllowance(address,uint256)".to_string()),
-			(
-				[0xd5, 0x05, 0xac, 0xcf],
-				"permit(address,address,uint256,uint256,uint8,bytes32,bytes32)".to_string(),
-			),
-			(
-				[0x0c, 0x53, 0xc5, 0x1c],
-				"executeMetaTransaction(address,bytes,bytes32,bytes32,uint8)".to_string(),
-			),
-			([0x8f, 0x32, 0xd5, 0x9b], "pause()".to_string()),
-			([0x3f, 0x4b, 0xa8, 0x3a], "unpause()".to_string()),
-			([0x24, 0x86, 0x17, 0x82], "hasRole(bytes32,address)".to_string()),
-			([0x2f, 0x2f, 0xf1, 0x5d], "grantRole(bytes32,address)".to_string()),
-		]);
-
-		Self { provider, function_signatures }
+	pub fn new(provider: Provider<Http>, decoder: Arc<RwLock<AbiDecoder>>) -> Self {
+		Self { revert_fetcher: RevertReasonFetcher::new(provider.clone()), provider, decoder }
 	}
 
 	pub async fn get_erc20_transaction(&self, tx_hash: H256) -> Result<Option<ERC20Transaction>> {
@@ -66,88 +54,88 @@ impl ERC20TransactionFetcher {
 			.map(|sig| [sig[0], sig[1], sig[2], sig[3]])
 			.unwrap_or_default();
 
-		let decoded_data = self.decode_input_data(&transaction.input, &method_sig)?;
+		let to = format!("0x{}", hex::encode(transaction.to.unwrap_or_default().as_bytes()));
+		let decoded_data = self.decode_input_data(&to, &transaction.input);
 		let transaction_gas = self.calculate_transaction_gas(&transaction, &receipt)?;
+		// Looked up before the calls below so the decoder lock is not held across them
+		let method_name = self
+			.decoder
+			.read()
+			.unwrap()
+			.function_signature(&method_sig)
+			.unwrap_or("unknown".to_string());
 
 		Ok(Some(ERC20Transaction {
 			transaction_type: self.get_transaction_type(&method_sig),
-			method_name: self
-				.function_signatures
-				.get(&method_sig)
-				.cloned()
-				.unwrap_or("unknown".to_string()),
+			method_name,
 			hash: format!("{:?}", transaction.hash),
 			block_number: receipt.block_number.unwrap_or_default().as_u64(),
 			status: receipt.status.map_or(false, |status| status.as_u32() == 1),
 			token_info: self.get_token_info(transaction.to.unwrap_or_default()).await?,
-			native_value: transaction.value.as_u128(),
-			token_value: decoded_data.get("value").and_then(|v| v.parse().ok()).unwrap_or_default(),
+			native_value: transaction.value.to_string(),
+			token_value: decoded_data.get("value").cloned().unwrap_or_else(|| "0".to_string()),
 			transaction_gas,
 			decoded_data,
-			error_message: self.get_error_message(&receipt),
+			error_message: self.get_error_message(&transaction, &receipt, &to).await,
 		}))
 	}
 
-	fn decode_input_data(
-		&self,
-		input: &[u8],
-		method_sig: &[u8; 4],
-	) -> Result<HashMap<String, String>> {
-		let mut decoded = HashMap::new();
-		if input.len() < 4 {
-			return Ok(decoded);
-		}
-
-		match method_sig {
-			[0xa9, 0x05, 0x9c, 0xbb] => {
-				// transfer(address,uint256)
-				self.decode_transfer_or_approve(&input[4..], &mut decoded)?;
-			},
-			[0x09, 0x5e, 0xa7, 0xb3] => {
-				// approve(address,uint256)
-				self.decode_transfer_or_approve(&input[4..], &mut decoded)?;
-			},
-			_ => {},
-		}
-
-		Ok(decoded)
+	/// Named call arguments as strings, e.g. `to` and `value` for `transfer`
+	fn decode_input_data(&self, to: &str, input: &[u8]) -> HashMap<String, String> {
+		self.decoder
+			.read()
+			.unwrap()
+			.decode_input(to, input)
+			.map(|decoded| {
+				decoded
+					.params
+					.into_iter()
+					.map(|param| {
+						let value = match param.value {
+							Value::String(value) => value,
+							value => value.to_string(),
+						};
+						(param.name, value)
+					})
+					.collect()
+			})
+			.unwrap_or_default()
 	}
 
-	fn decode_transfer_or_approve(
-		&self,
-		data: &[u8],
-		decoded: &mut HashMap<String, String>,
-	) -> Result<()> {
-		if data.len() >= 64 {
-			let to = format!("0x{}", hex::encode(&data[12..32]));
-			let value = U256::from_big_endian(&data[32..64]).to_string();
-			decoded.insert("to".to_string(), to);
-			decoded.insert("value".to_string(), value);
-		}
-		Ok(())
+	/// Token metadata, with the four calls sent concurrently
+	pub async fn get_token_info(&self, token_address: Address) -> Result<Erc20Info> {
+		let contract = self.contract(token_address);
+
+		let name = contract.method::<_, String>("name", ())?;
+		let symbol = contract.method::<_, String>("symbol", ())?;
+		let decimals = contract.method::<_, u8>("decimals", ())?;
+		let total_supply = contract.method::<_, U256>("totalSupply", ())?;
+		let (name, symbol, decimals, total_supply) =
+			futures::join!(name.call(), symbol.call(), decimals.call(), total_supply.call());
+
+		Ok(Erc20Info {
+			name: name.unwrap_or("Unknown".to_string()),
+			symbol: symbol.unwrap_or("UNK".to_string()),
+			decimals: decimals.unwrap_or(18),
+			total_supply: total_supply.unwrap_or_default().to_string(),
+		})
 	}
 
-	async fn get_token_info(&self, token_address: Address) -> Result<Erc20Info> {
-		let abi: ethers::abi::Abi = serde_json::from_str(ERC20_ABI)?;
-		let contract = Contract::new(token_address, abi, Arc::new(self.provider.clone()));
-
-		let name = contract
-			.method::<_, String>("name", ())?
-			.call()
-			.await
-			.unwrap_or("Unknown".to_string());
-		let symbol = contract
-			.method::<_, String>("symbol", ())?
+	pub async fn get_total_supply(&self, token_address: Address) -> Result<U256> {
+		let total_supply = self
+			.contract(token_address)
+			.method::<_, U256>("totalSupply", ())?
 			.call()
-			.await
-			.unwrap_or("UNK".to_string());
-		let decimals = contract.method::<_, u8>("decimals", ())?.call().await.unwrap_or(18);
+			.await?;
+		Ok(total_supply)
+	}
 
-		Ok(Erc20Info { name, symbol, decimals })
+	fn contract(&self, token_address: Address) -> Contract<Provider<Http>> {
+		Contract::new(token_address, ERC20_CONTRACT_ABI.clone(), Arc::new(self.provider.clone()))
 	}
 
 	fn get_transaction_type(&self, method_sig: &[u8; 4]) -> String {
-		match self.function_signatures.get(method_sig) {
+		match self.decoder.read().unwrap().function_signature(method_sig) {
 			Some(name) if name.starts_with("transfer") => "Transfer".to_string(),
 			Some(name) if name.starts_with("approve") => "Approve".to_string(),
 			Some(name) if name.starts_with("mint") => "Mint".to_string(),
@@ -164,28 +152,29 @@ impl ERC20TransactionFetcher {
 		receipt: &TransactionReceipt,
 	) -> Result<TransactionGas> {
 		let gas_used = receipt.gas_used.unwrap_or_default();
-		let gas_price = transaction.gas_price.unwrap_or_default();
-		let total_gas_cost = (gas_used * gas_price).as_u64();
+		let gas_price = receipt.effective_gas_price.or(transaction.gas_price).unwrap_or_default();
+		// Widened to U512 so the product can never overflow
+		let total_gas_cost = gas_used.full_mul(gas_price).to_string();
 
 		Ok(TransactionGas {
-			gas_price: gas_price.as_u128(),
-			gas_used: gas_used.as_u64(),
+			gas_price: gas_price.to_string(),
+			gas_used: gas_used.low_u64(),
 			total_gas_cost,
 		})
 	}
 
-	fn get_error_message(&self, receipt: &TransactionReceipt) -> Option<String> {
+	/// Decoded revert reason of a failed transaction, replayed since receipts carry none
+	async fn get_error_message(
+		&self,
+		transaction: &Transaction,
+		receipt: &TransactionReceipt,
+		to: &str,
+	) -> Option<String> {
 		if receipt.status.unwrap_or_default().as_u64() == 0 {
-			receipt
-				.logs
-				.first()
-				.and_then(|log| {
-					if log.data.len() >= 68 {
-						Some(String::from_utf8_lossy(&log.data[36..68]).to_string())
-					} else {
-						None
-					}
-				})
+			self.revert_fetcher
+				.get_revert_data(transaction)
+				.await
+				.and_then(|data| self.decoder.read().unwrap().decode_revert(to, &data))
 				.or(Some("Transaction Failed".to_string()))
 		} else {
 			None
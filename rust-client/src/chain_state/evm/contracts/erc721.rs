@@ -0,0 +1,37 @@
+use anyhow::Result;
+use std::sync::Arc;
+
+use ethers::{
+	contract::Contract,
+	providers::{Http, Provider},
+	types::{Address, U256},
+};
+
+use super::abi::ERC721_ABI;
+
+#[derive(Debug, Clone)]
+pub struct ERC721MetadataFetcher {
+	pub provider: Provider<Http>,
+}
+
+impl ERC721MetadataFetcher {
+	pub fn new(provider: Provider<Http>) -> Self {
+		Self { provider }
+	}
+
+	/// Metadata URI of a token, `None` when the contract does not implement `tokenURI(uint256)`
+	/// or the token does not exist
+	pub async fn get_token_uri(
+		&self,
+		token_address: Address,
+		token_id: U256,
+	) -> Result<Option<String>> {
+		let abi: ethers::abi::Abi = serde_json::from_str(ERC721_ABI)?;
+		let contract = Contract::new(token_address, abi, Arc::new(self.provider.clone()));
+
+		match contract.method::<_, String>("tokenURI", token_id)?.call().await {
+			Ok(uri) if !uri.is_empty() => Ok(Some(uri)),
+			_ => Ok(None),
+		}
+	}
+}
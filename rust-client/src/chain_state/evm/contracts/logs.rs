@@ -0,0 +1,261 @@
+use ethers::{
+	abi::{decode, ParamType, Token},
+	types::{Log, H256, U256},
+	utils::keccak256,
+};
+use once_cell::sync::Lazy;
+
+use crate::models::{contract::TokenTransfer, extrinsic::EvmLog};
+
+pub static TRANSFER_TOPIC: Lazy<H256> =
+	Lazy::new(|| H256::from(keccak256("Transfer(address,address,uint256)")));
+pub static APPROVAL_TOPIC: Lazy<H256> =
+	Lazy::new(|| H256::from(keccak256("Approval(address,address,uint256)")));
+pub static TRANSFER_SINGLE_TOPIC: Lazy<H256> =
+	Lazy::new(|| H256::from(keccak256("TransferSingle(address,address,address,uint256,uint256)")));
+pub static TRANSFER_BATCH_TOPIC: Lazy<H256> = Lazy::new(|| {
+	H256::from(keccak256("TransferBatch(address,address,address,uint256[],uint256[])"))
+});
+
+pub fn to_evm_log(log: &Log) -> EvmLog {
+	EvmLog {
+		address: format!("0x{}", hex::encode(log.address.as_bytes())),
+		topics: log.topics.iter().map(|topic| format!("0x{}", hex::encode(topic))).collect(),
+		data: format!("0x{}", hex::encode(&log.data)),
+		log_index: log.log_index.unwrap_or_default().low_u64(),
+	}
+}
+
+/// Decode the token movements in a log: ERC-20 `Transfer`/`Approval`, ERC-721 `Transfer` and
+/// ERC-1155 `TransferSingle`/`TransferBatch`. A batch yields one entry per token id.
+pub fn decode_token_events(log: &Log) -> Vec<TokenTransfer> {
+	match log.topics.first() {
+		Some(topic) if *topic == *TRANSFER_TOPIC && log.topics.len() == 4 =>
+			decode_erc721_transfer(log).into_iter().collect(),
+		Some(topic) if *topic == *TRANSFER_SINGLE_TOPIC =>
+			decode_transfer_single(log).into_iter().collect(),
+		Some(topic) if *topic == *TRANSFER_BATCH_TOPIC => decode_transfer_batch(log),
+		_ => decode_erc20_event(log).into_iter().collect(),
+	}
+}
+
+/// Decode an ERC-20 `Transfer` or `Approval` log.
+///
+/// ERC-721 uses the same `Transfer` signature with the token id as a third indexed topic, so
+/// only logs with two indexed addresses and a single word of data are accepted.
+fn decode_erc20_event(log: &Log) -> Option<TokenTransfer> {
+	let event = match log.topics.first()? {
+		topic if *topic == *TRANSFER_TOPIC => "Transfer",
+		topic if *topic == *APPROVAL_TOPIC => "Approval",
+		_ => return None,
+	};
+
+	if log.topics.len() != 3 || log.data.len() != 32 {
+		return None;
+	}
+
+	Some(TokenTransfer {
+		token_address: format!("0x{}", hex::encode(log.address.as_bytes())),
+		event: event.to_string(),
+		from: topic_to_address(&log.topics[1]),
+		to: topic_to_address(&log.topics[2]),
+		value: U256::from_big_endian(&log.data).to_string(),
+		token_id: None,
+		log_index: log.log_index.unwrap_or_default().low_u64(),
+		batch_index: None,
+	})
+}
+
+/// ERC-721 `Transfer(from, to, tokenId)` with all three indexed and no data
+fn decode_erc721_transfer(log: &Log) -> Option<TokenTransfer> {
+	if log.topics.len() != 4 || !log.data.is_empty() {
+		return None;
+	}
+
+	Some(TokenTransfer {
+		token_address: format!("0x{}", hex::encode(log.address.as_bytes())),
+		event: "Transfer".to_string(),
+		from: topic_to_address(&log.topics[1]),
+		to: topic_to_address(&log.topics[2]),
+		value: "1".to_string(),
+		token_id: Some(U256::from_big_endian(log.topics[3].as_bytes()).to_string()),
+		log_index: log.log_index.unwrap_or_default().low_u64(),
+		batch_index: None,
+	})
+}
+
+/// `TransferSingle(operator, from, to, id, value)` with operator, from and to indexed
+fn decode_transfer_single(log: &Log) -> Option<TokenTransfer> {
+	if log.topics.len() != 4 || log.data.len() != 64 {
+		return None;
+	}
+
+	Some(TokenTransfer {
+		token_address: format!("0x{}", hex::encode(log.address.as_bytes())),
+		event: "TransferSingle".to_string(),
+		from: topic_to_address(&log.topics[2]),
+		to: topic_to_address(&log.topics[3]),
+		value: U256::from_big_endian(&log.data[32..64]).to_string(),
+		token_id: Some(U256::from_big_endian(&log.data[0..32]).to_string()),
+		log_index: log.log_index.unwrap_or_default().low_u64(),
+		batch_index: None,
+	})
+}
+
+/// `TransferBatch(operator, from, to, ids, values)` with the two arrays ABI encoded in data
+fn decode_transfer_batch(log: &Log) -> Vec<TokenTransfer> {
+	if log.topics.len() != 4 {
+		return Vec::new();
+	}
+
+	let array = ParamType::Array(Box::new(ParamType::Uint(256)));
+	let (ids, values) = match decode(&[array.clone(), array], &log.data).as_deref() {
+		Ok([Token::Array(ids), Token::Array(values)]) if ids.len() == values.len() =>
+			(ids.clone(), values.clone()),
+		_ => return Vec::new(),
+	};
+
+	ids.into_iter()
+		.zip(values)
+		.enumerate()
+		.filter_map(|(index, (id, value))| {
+			Some(TokenTransfer {
+				token_address: format!("0x{}", hex::encode(log.address.as_bytes())),
+				event: "TransferBatch".to_string(),
+				from: topic_to_address(&log.topics[2]),
+				to: topic_to_address(&log.topics[3]),
+				value: value.into_uint()?.to_string(),
+				token_id: Some(id.into_uint()?.to_string()),
+				log_index: log.log_index.unwrap_or_default().low_u64(),
+				batch_index: Some(index as u32),
+			})
+		})
+		.collect()
+}
+
+fn topic_to_address(topic: &H256) -> String {
+	format!("0x{}", hex::encode(&topic.as_bytes()[12..]))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethers::types::{Address, Bytes};
+
+	fn address_topic(address: Address) -> H256 {
+		H256::from(address)
+	}
+
+	fn transfer_log(topics: Vec<H256>, value: U256) -> Log {
+		let mut data = [0u8; 32];
+		value.to_big_endian(&mut data);
+		Log {
+			address: Address::repeat_byte(0xaa),
+			topics,
+			data: Bytes::from(data.to_vec()),
+			log_index: Some(U256::from(7)),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn test_decode_erc20_transfer() {
+		let from = Address::repeat_byte(0x11);
+		let to = Address::repeat_byte(0x22);
+		let value = U256::MAX;
+		let log =
+			transfer_log(vec![*TRANSFER_TOPIC, address_topic(from), address_topic(to)], value);
+
+		let transfer = decode_erc20_event(&log).expect("transfer should decode");
+		assert_eq!(transfer.event, "Transfer");
+		assert_eq!(transfer.token_address, format!("0x{}", "aa".repeat(20)));
+		assert_eq!(transfer.from, format!("0x{}", "11".repeat(20)));
+		assert_eq!(transfer.to, format!("0x{}", "22".repeat(20)));
+		assert_eq!(transfer.value, U256::MAX.to_string());
+		assert_eq!(transfer.log_index, 7);
+	}
+
+	#[test]
+	fn test_decode_erc20_approval() {
+		let log = transfer_log(
+			vec![
+				*APPROVAL_TOPIC,
+				address_topic(Address::repeat_byte(0x11)),
+				address_topic(Address::repeat_byte(0x22)),
+			],
+			U256::from(1_000u64),
+		);
+
+		let approval = decode_erc20_event(&log).expect("approval should decode");
+		assert_eq!(approval.event, "Approval");
+		assert_eq!(approval.value, "1000");
+	}
+
+	#[test]
+	fn test_decode_erc721_transfer() {
+		let mut log = transfer_log(
+			vec![
+				*TRANSFER_TOPIC,
+				address_topic(Address::repeat_byte(0x11)),
+				address_topic(Address::repeat_byte(0x22)),
+				H256::from_low_u64_be(42),
+			],
+			U256::zero(),
+		);
+		log.data = Bytes::default();
+
+		// Not an ERC-20 transfer, but an NFT with the id in the last topic
+		assert!(decode_erc20_event(&log).is_none());
+		let transfers = decode_token_events(&log);
+		assert_eq!(transfers.len(), 1);
+		assert_eq!(transfers[0].token_id.as_deref(), Some("42"));
+		assert_eq!(transfers[0].value, "1");
+	}
+
+	fn erc1155_topics(signature: H256) -> Vec<H256> {
+		vec![
+			signature,
+			address_topic(Address::repeat_byte(0x33)),
+			address_topic(Address::repeat_byte(0x11)),
+			address_topic(Address::repeat_byte(0x22)),
+		]
+	}
+
+	#[test]
+	fn test_decode_erc1155_transfer_single() {
+		let data = ethers::abi::encode(&[Token::Uint(U256::from(5)), Token::Uint(U256::from(3))]);
+		let log = Log {
+			topics: erc1155_topics(*TRANSFER_SINGLE_TOPIC),
+			data: Bytes::from(data),
+			..Default::default()
+		};
+
+		let transfers = decode_token_events(&log);
+		assert_eq!(transfers.len(), 1);
+		assert_eq!(transfers[0].event, "TransferSingle");
+		assert_eq!(transfers[0].from, format!("0x{}", "11".repeat(20)));
+		assert_eq!(transfers[0].to, format!("0x{}", "22".repeat(20)));
+		assert_eq!(transfers[0].token_id.as_deref(), Some("5"));
+		assert_eq!(transfers[0].value, "3");
+	}
+
+	#[test]
+	fn test_decode_erc1155_transfer_batch() {
+		let data = ethers::abi::encode(&[
+			Token::Array(vec![Token::Uint(U256::from(1)), Token::Uint(U256::from(2))]),
+			Token::Array(vec![Token::Uint(U256::from(10)), Token::Uint(U256::from(20))]),
+		]);
+		let log = Log {
+			topics: erc1155_topics(*TRANSFER_BATCH_TOPIC),
+			data: Bytes::from(data),
+			..Default::default()
+		};
+
+		let transfers = decode_token_events(&log);
+		assert_eq!(transfers.len(), 2);
+		assert_eq!(transfers[1].event, "TransferBatch");
+		assert_eq!(transfers[1].token_id.as_deref(), Some("2"));
+		assert_eq!(transfers[1].value, "20");
+		assert_eq!(transfers[1].batch_index, Some(1));
+	}
+}
@@ -1,3 +1,8 @@
 pub mod abi;
+pub mod decoder;
+pub mod erc1155;
 pub mod erc20;
+pub mod erc721;
+pub mod logs;
+pub mod signatures;
 pub mod types;
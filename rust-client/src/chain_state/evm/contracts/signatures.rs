@@ -0,0 +1,94 @@
+//! Bundled human-readable signatures used to decode calls and logs of contracts without a
+//! registered ABI. ERC-20 and ERC-721 share several selectors; the ERC-20 parameter names are
+//! kept for those.
+
+pub const FUNCTION_SIGNATURES: &[&str] = &[
+	// ERC-20
+	"function transfer(address to, uint256 value)",
+	"function transferFrom(address from, address to, uint256 value)",
+	"function approve(address spender, uint256 value)",
+	"function balanceOf(address owner)",
+	"function allowance(address owner, address spender)",
+	"function totalSupply()",
+	"function increaseAllowance(address spender, uint256 addedValue)",
+	"function decreaseAllowance(address spender, uint256 subtractedValue)",
+	"function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s)",
+	"function mint(address to, uint256 value)",
+	"function burn(uint256 value)",
+	"function burnFrom(address account, uint256 value)",
+	// ERC-721
+	"function ownerOf(uint256 tokenId)",
+	"function safeTransferFrom(address from, address to, uint256 tokenId)",
+	"function safeTransferFrom(address from, address to, uint256 tokenId, bytes data)",
+	"function setApprovalForAll(address operator, bool approved)",
+	"function isApprovedForAll(address owner, address operator)",
+	"function getApproved(uint256 tokenId)",
+	"function tokenURI(uint256 tokenId)",
+	"function safeMint(address to, uint256 tokenId)",
+	// ERC-1155
+	"function safeTransferFrom(address from, address to, uint256 id, uint256 value, bytes data)",
+	"function safeBatchTransferFrom(address from, address to, uint256[] ids, uint256[] values, bytes data)",
+	"function balanceOfBatch(address[] accounts, uint256[] ids)",
+	"function uri(uint256 id)",
+	// ERC-777
+	"function send(address to, uint256 amount, bytes data)",
+	"function operatorSend(address from, address to, uint256 amount, bytes data, bytes operatorData)",
+	"function authorizeOperator(address operator)",
+	"function revokeOperator(address operator)",
+	// Wrapped native token
+	"function deposit()",
+	"function withdraw(uint256 amount)",
+	// Ownership and access control
+	"function transferOwnership(address newOwner)",
+	"function renounceOwnership()",
+	"function grantRole(bytes32 role, address account)",
+	"function revokeRole(bytes32 role, address account)",
+	"function renounceRole(bytes32 role, address account)",
+	"function hasRole(bytes32 role, address account)",
+	"function pause()",
+	"function unpause()",
+	// Proxies, multicall and meta transactions
+	"function upgradeTo(address newImplementation)",
+	"function upgradeToAndCall(address newImplementation, bytes data)",
+	"function multicall(bytes[] data)",
+	"function executeMetaTransaction(address userAddress, bytes functionSignature, bytes32 sigR, bytes32 sigS, uint8 sigV)",
+	// Uniswap V2 style routers
+	"function swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline)",
+	"function swapTokensForExactTokens(uint256 amountOut, uint256 amountInMax, address[] path, address to, uint256 deadline)",
+	"function swapExactETHForTokens(uint256 amountOutMin, address[] path, address to, uint256 deadline)",
+	"function swapETHForExactTokens(uint256 amountOut, address[] path, address to, uint256 deadline)",
+	"function swapExactTokensForETH(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline)",
+	"function swapTokensForExactETH(uint256 amountOut, uint256 amountInMax, address[] path, address to, uint256 deadline)",
+	"function addLiquidity(address tokenA, address tokenB, uint256 amountADesired, uint256 amountBDesired, uint256 amountAMin, uint256 amountBMin, address to, uint256 deadline)",
+	"function addLiquidityETH(address token, uint256 amountTokenDesired, uint256 amountTokenMin, uint256 amountETHMin, address to, uint256 deadline)",
+	"function removeLiquidity(address tokenA, address tokenB, uint256 liquidity, uint256 amountAMin, uint256 amountBMin, address to, uint256 deadline)",
+	"function removeLiquidityETH(address token, uint256 liquidity, uint256 amountTokenMin, uint256 amountETHMin, address to, uint256 deadline)",
+];
+
+pub const EVENT_SIGNATURES: &[&str] = &[
+	// ERC-20 and ERC-721 share the Transfer and Approval topics and differ in indexed params
+	"event Transfer(address indexed from, address indexed to, uint256 value)",
+	"event Transfer(address indexed from, address indexed to, uint256 indexed tokenId)",
+	"event Approval(address indexed owner, address indexed spender, uint256 value)",
+	"event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId)",
+	"event ApprovalForAll(address indexed owner, address indexed operator, bool approved)",
+	"event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value)",
+	"event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values)",
+	"event URI(string value, uint256 indexed id)",
+	"event Sent(address indexed operator, address indexed from, address indexed to, uint256 amount, bytes data, bytes operatorData)",
+	"event Minted(address indexed operator, address indexed to, uint256 amount, bytes data, bytes operatorData)",
+	"event Burned(address indexed operator, address indexed from, uint256 amount, bytes data, bytes operatorData)",
+	"event Deposit(address indexed dst, uint256 wad)",
+	"event Withdrawal(address indexed src, uint256 wad)",
+	"event OwnershipTransferred(address indexed previousOwner, address indexed newOwner)",
+	"event RoleGranted(bytes32 indexed role, address indexed account, address indexed sender)",
+	"event RoleRevoked(bytes32 indexed role, address indexed account, address indexed sender)",
+	"event Paused(address account)",
+	"event Unpaused(address account)",
+	"event Upgraded(address indexed implementation)",
+	"event Swap(address indexed sender, uint256 amount0In, uint256 amount1In, uint256 amount0Out, uint256 amount1Out, address indexed to)",
+	"event Sync(uint112 reserve0, uint112 reserve1)",
+	"event Mint(address indexed sender, uint256 amount0, uint256 amount1)",
+	"event Burn(address indexed sender, uint256 amount0, uint256 amount1, address indexed to)",
+	"event PairCreated(address indexed token0, address indexed token1, address pair, uint256)",
+];
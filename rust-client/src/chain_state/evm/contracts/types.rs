@@ -1,139 +1,159 @@
 use ethers::{
-	abi::{encode, Token},
+	abi::{encode, Abi, Token},
 	providers::{Http, Middleware, Provider, ProviderError},
-	types::{transaction::eip2718::TypedTransaction, Address, TransactionRequest, U256},
+	types::{transaction::eip2718::TypedTransaction, Address, BlockId, TransactionRequest, H256},
 	utils::keccak256,
 };
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{Arc, Mutex},
+};
 
 use crate::models::contract::ContractType;
 
+const ERC165_INTERFACE_ID: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+const ERC721_INTERFACE_ID: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+const ERC1155_INTERFACE_ID: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+// ERC-165 requires `supportsInterface(0xffffffff)` to return false
+const INVALID_INTERFACE_ID: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+
+const ERC20_FUNCTIONS: [&str; 6] = [
+	"totalSupply()",
+	"balanceOf(address)",
+	"transfer(address,uint256)",
+	"transferFrom(address,address,uint256)",
+	"approve(address,uint256)",
+	"allowance(address,address)",
+];
+const ERC721_FUNCTIONS: [&str; 6] = [
+	"balanceOf(address)",
+	"ownerOf(uint256)",
+	"transferFrom(address,address,uint256)",
+	"approve(address,uint256)",
+	"setApprovalForAll(address,bool)",
+	"isApprovedForAll(address,address)",
+];
+
+const ERC1155_FUNCTIONS: [&str; 6] = [
+	"balanceOf(address,uint256)",
+	"balanceOfBatch(address[],uint256[])",
+	"setApprovalForAll(address,bool)",
+	"isApprovedForAll(address,address)",
+	"safeTransferFrom(address,address,uint256,uint256,bytes)",
+	"safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+];
+// ERC-777 registers through ERC-1820 rather than ERC-165, so it is only found by selectors
+const ERC777_FUNCTIONS: [&str; 6] = [
+	"granularity()",
+	"defaultOperators()",
+	"send(address,uint256,bytes)",
+	"operatorSend(address,address,uint256,bytes,bytes)",
+	"authorizeOperator(address)",
+	"revokeOperator(address)",
+];
+
+const PUSH1: u8 = 0x60;
+const PUSH4: u8 = 0x63;
+const PUSH32: u8 = 0x7f;
+
 #[derive(Debug, Clone)]
 pub struct ContractDetector {
 	pub provider: Provider<Http>,
+	// (address, code hash) -> detected type, so a contract is only probed once
+	cache: Arc<Mutex<HashMap<(Address, H256), ContractType>>>,
 }
 
 impl ContractDetector {
 	pub fn new(provider: Provider<Http>) -> Self {
-		Self { provider }
+		Self { provider, cache: Arc::new(Mutex::new(HashMap::new())) }
 	}
 
-	/// Detect and return contract metadata
-	pub async fn detect(&self, address: Address) -> Result<Option<ContractType>, ProviderError> {
-		// Check for deployed contract code
-		if !self.has_contract_code(address).await? {
-			println!("No contract code found at: {:?}", address);
+	/// Detect the token standard of a contract as of `block_number`, or the latest block when
+	/// `None`; returns `None` when no code is deployed
+	pub async fn detect(
+		&self,
+		address: Address,
+		block_number: Option<u64>,
+	) -> Result<Option<ContractType>, ProviderError> {
+		let block = block_number.map(BlockId::from);
+		let code = self.provider.get_code(address, block).await?;
+		if code.is_empty() {
 			return Ok(None);
 		}
 
-		if let Some(erc20) = self.detect_erc20(address).await? {
-			return Ok(Some(erc20));
+		let key = (address, H256::from(keccak256(&code)));
+		if let Some(contract_type) = self.cache.lock().unwrap().get(&key) {
+			return Ok(Some(contract_type.clone()));
 		}
 
-		if let Some(erc721) = self.detect_erc721(address).await? {
-			return Ok(Some(erc721));
-		}
+		let contract_type = match self.detect_by_interface(address, block).await {
+			Some(contract_type) => contract_type,
+			None => detect_by_selectors(&code),
+		};
+		self.cache.lock().unwrap().insert(key, contract_type.clone());
 
-		Ok(Some(ContractType::Unknown))
+		Ok(Some(contract_type))
 	}
 
-	/// Detect ERC20 contract and fetch metadata
-	async fn detect_erc20(&self, address: Address) -> Result<Option<ContractType>, ProviderError> {
-		let methods = ["name()", "symbol()", "decimals()", "totalSupply()"];
-
-		if !self.validate_methods(address, &methods).await {
-			return Ok(None); // Not an ERC20
+	/// Signatures of the functions in `abi` that the code deployed at `address` does not
+	/// dispatch to, `None` when no code is deployed
+	pub async fn unmatched_functions(
+		&self,
+		address: Address,
+		abi: &Abi,
+	) -> Result<Option<Vec<String>>, ProviderError> {
+		let code = self.provider.get_code(address, None).await?;
+		if code.is_empty() {
+			return Ok(None);
 		}
+		Ok(Some(missing_functions(&code, abi)))
+	}
 
-		let transafer = self
-			.validate_function_call(
-				address,
-				"transfer(address,uint256)",
-				vec![Token::Address(Address::zero()), Token::Uint(U256::zero())],
-			)
-			.await
-			.unwrap_or(false);
-
-		let balance_of = self
-			.validate_function_call(
-				address,
-				"balanceOf(address)",
-				vec![Token::Address(Address::zero())],
-			)
-			.await
-			.unwrap_or(false);
-
-		let allowance = self
-			.validate_function_call(
-				address,
-				"allowance(address,address)",
-				vec![Token::Address(Address::zero()), Token::Address(Address::zero())],
-			)
-			.await
-			.unwrap_or(false);
-
-		if !transafer || !balance_of || !allowance {
-			return Ok(None); // Not an ERC20
+	/// Standards advertised through ERC-165, `None` when the contract does not implement it
+	/// or advertises none we know.
+	async fn detect_by_interface(
+		&self,
+		address: Address,
+		block: Option<BlockId>,
+	) -> Option<ContractType> {
+		if !self.supports_interface(address, ERC165_INTERFACE_ID, block).await
+			|| self.supports_interface(address, INVALID_INTERFACE_ID, block).await
+		{
+			return None;
 		}
 
-		Ok(Some(ContractType::ERC20))
-	}
-
-	async fn detect_erc721(&self, address: Address) -> Result<Option<ContractType>, ProviderError> {
-		// Required ERC721 methods
-		let methods =
-			["name()", "symbol()", "balanceOf(address)", "ownerOf(uint256)", "tokenURI(uint256)"];
+		if self.supports_interface(address, ERC721_INTERFACE_ID, block).await {
+			return Some(ContractType::ERC721);
+		}
 
-		if !self.validate_methods(address, &methods).await {
-			return Ok(None); // Not an ERC20
+		if self.supports_interface(address, ERC1155_INTERFACE_ID, block).await {
+			return Some(ContractType::ERC1155);
 		}
 
-		Ok(Some(ContractType::ERC721))
+		None
 	}
 
-	/// Generic function call validation
-	async fn validate_function_call(
+	/// Call `supportsInterface(bytes4)`; reverts and malformed results count as unsupported
+	async fn supports_interface(
 		&self,
 		address: Address,
-		func_sig: &str,
-		params: Vec<Token>,
-	) -> Result<bool, ProviderError> {
-		let tx = self.create_call(address, func_sig, params, Some(50_000));
-		match self.provider.call(&tx, None).await {
-			Ok(_) => Ok(true), // Function exists and callable
-			Err(e) if e.to_string().contains("revert") => Ok(true), // Function exists but reverts
-			Err(_) => Ok(false), // Function does not exist
-		}
-	}
+		interface_id: [u8; 4],
+		block: Option<BlockId>,
+	) -> bool {
+		// ERC-165 caps the query at 30,000 gas
+		let tx = self.create_call(
+			address,
+			"supportsInterface(bytes4)",
+			vec![Token::FixedBytes(interface_id.to_vec())],
+			Some(30_000),
+		);
 
-	/// Validate multiple methods
-	async fn validate_methods(&self, address: Address, methods: &[&str]) -> bool {
-		for &method in methods {
-			let tx = self.create_call(address, method, vec![], None);
-			match self.provider.call(&tx, None).await {
-				Ok(data) => !data.is_empty(), // Ensure non-empty response
-				Err(_) => false,
-			};
+		match self.provider.call(&tx, block).await {
+			Ok(data) => data.len() == 32 && data[..31].iter().all(|b| *b == 0) && data[31] == 1,
+			Err(_) => false,
 		}
-		true
 	}
 
-	/// Check if the contract has deployed code
-	async fn has_contract_code(&self, address: Address) -> Result<bool, ProviderError> {
-		let code = self.provider.get_code(address, None).await?;
-		Ok(!code.is_empty())
-	}
-
-	// /// Check if contract supports specific interface
-	// async fn check_interface_support(&self, address: Address, interface_id: &str) -> Result<bool, ProviderError> {
-	//     let interface_bytes = hex::decode(interface_id).unwrap_or_default();
-
-	//     self.validate_function_call(
-	//         address,
-	//         "supportsInterface(bytes4)",
-	//         vec![Token::FixedBytes(interface_bytes)],
-	//     ).await
-	// }
-
 	/// Create a call transaction
 	fn create_call(
 		&self,
@@ -142,8 +162,7 @@ impl ContractDetector {
 		params: Vec<Token>,
 		gas: Option<u64>,
 	) -> TypedTransaction {
-		let selector = &keccak256(func_sig.as_bytes())[0..4];
-		let mut data = selector.to_vec();
+		let mut data = selector(func_sig).to_vec();
 		if !params.is_empty() {
 			data.extend_from_slice(&encode(&params));
 		}
@@ -157,11 +176,77 @@ impl ContractDetector {
 	}
 }
 
+/// Match the function selectors in the dispatcher against each standard's required functions.
+/// ERC-721 and ERC-777 are checked before ERC-20, whose functions they also implement.
+fn detect_by_selectors(code: &[u8]) -> ContractType {
+	let selectors = scan_selectors(code);
+	let implements =
+		|functions: &[&str]| functions.iter().all(|f| selectors.contains(&selector(f)));
+
+	if implements(&ERC1155_FUNCTIONS) {
+		ContractType::ERC1155
+	} else if implements(&ERC721_FUNCTIONS) {
+		ContractType::ERC721
+	} else if implements(&ERC777_FUNCTIONS) {
+		ContractType::ERC777
+	} else if implements(&ERC20_FUNCTIONS) {
+		ContractType::ERC20
+	} else {
+		ContractType::Unknown
+	}
+}
+
+/// Signatures of the functions in `abi` whose selector is not pushed anywhere in `code`
+fn missing_functions(code: &[u8], abi: &Abi) -> Vec<String> {
+	let selectors = scan_selectors(code);
+	abi.functions()
+		.filter(|function| !selectors.contains(&function.short_signature()))
+		.map(|function| {
+			let inputs: Vec<String> =
+				function.inputs.iter().map(|input| input.kind.to_string()).collect();
+			format!("{}({})", function.name, inputs.join(","))
+		})
+		.collect()
+}
+
+/// Every `PUSH1` to `PUSH4` operand in the bytecode, left-padded to 4 bytes, where Solidity
+/// and Vyper dispatchers keep function selectors. Selectors with leading zero bytes, such as
+/// ERC-1155 `balanceOf` (`0x00fdd58e`), are pushed with the shortest opcode that fits. Other
+/// push data is skipped so it is not misread as opcodes.
+fn scan_selectors(code: &[u8]) -> HashSet<[u8; 4]> {
+	let mut selectors = HashSet::new();
+	let mut pc = 0;
+	while pc < code.len() {
+		let opcode = code[pc];
+		if (PUSH1..=PUSH4).contains(&opcode) {
+			let size = (opcode - PUSH1 + 1) as usize;
+			if let Some(operand) = code.get(pc + 1..pc + 1 + size) {
+				let mut selector = [0u8; 4];
+				selector[4 - size..].copy_from_slice(operand);
+				selectors.insert(selector);
+			}
+		}
+		if (PUSH1..=PUSH32).contains(&opcode) {
+			pc += (opcode - PUSH1 + 1) as usize;
+		}
+		pc += 1;
+	}
+	selectors
+}
+
+fn selector(func_sig: &str) -> [u8; 4] {
+	let mut selector = [0u8; 4];
+	selector.copy_from_slice(&keccak256(func_sig.as_bytes())[0..4]);
+	selector
+}
+
 // Tests
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	const PUSH3: u8 = 0x62;
+
 	#[tokio::test]
 	async fn test_detect_erc20() {
 		let provider = Provider::<Http>::try_from(
@@ -171,7 +256,7 @@ mod tests {
 		let detector = ContractDetector::new(provider);
 		let token_address: Address = "0xdAC17F958D2ee523a2206206994597C13D831ec7".parse().unwrap(); // USDT
 
-		match detector.detect(token_address).await {
+		match detector.detect(token_address, None).await {
 			Ok(Some(metadata)) => {
 				println!("Token contract: {:?}", metadata);
 				assert_eq!(metadata, ContractType::ERC20);
@@ -190,7 +275,7 @@ mod tests {
 		let non_erc20_address: Address =
 			"0x0000000000000000000000000000000000000000".parse().unwrap();
 
-		match detector.detect(non_erc20_address).await {
+		match detector.detect(non_erc20_address, None).await {
 			Ok(Some(_)) => panic!("Address should not be detected as an ERC20 contract"),
 			Ok(None) => println!("Address is not a valid ERC20 contract as expected."),
 			Err(e) => panic!("Error: {:?}", e),
@@ -205,9 +290,9 @@ mod tests {
 		.expect("Failed to create provider");
 
 		let detector = ContractDetector::new(provider);
-		let token_address: Address = "0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB".parse().unwrap(); // CryptoPunks
+		let token_address: Address = "0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D".parse().unwrap(); // BAYC
 
-		match detector.detect(token_address).await {
+		match detector.detect(token_address, None).await {
 			Ok(Some(metadata)) => {
 				println!("NFT contract: {:?}", metadata);
 				assert_eq!(metadata, ContractType::ERC721);
@@ -216,4 +301,72 @@ mod tests {
 			Err(e) => panic!("Error: {:?}", e),
 		}
 	}
+
+	// Pushes each selector with the shortest opcode, as solc does
+	fn dispatcher(functions: &[&str]) -> Vec<u8> {
+		let mut code = Vec::new();
+		for function in functions {
+			let function_selector = selector(function);
+			let leading_zeros =
+				function_selector.iter().take(3).take_while(|byte| **byte == 0).count();
+			code.push(PUSH4 - leading_zeros as u8);
+			code.extend_from_slice(&function_selector[leading_zeros..]);
+		}
+		code
+	}
+
+	#[test]
+	fn test_detect_by_selectors() {
+		assert_eq!(detect_by_selectors(&dispatcher(&ERC20_FUNCTIONS)), ContractType::ERC20);
+		assert_eq!(detect_by_selectors(&dispatcher(&ERC721_FUNCTIONS)), ContractType::ERC721);
+		assert_eq!(detect_by_selectors(&dispatcher(&ERC1155_FUNCTIONS)), ContractType::ERC1155);
+
+		// ERC-777 tokens are usually ERC-20 compatible as well
+		let erc777 = [ERC20_FUNCTIONS, ERC777_FUNCTIONS].concat();
+		assert_eq!(detect_by_selectors(&dispatcher(&erc777)), ContractType::ERC777);
+		assert_eq!(detect_by_selectors(&dispatcher(&ERC20_FUNCTIONS[1..])), ContractType::Unknown);
+	}
+
+	#[test]
+	fn test_scan_selectors_skips_push_data() {
+		let transfer = selector("transfer(address,uint256)");
+
+		// PUSH4 inside PUSH32 data is not an opcode
+		let mut code = vec![PUSH32];
+		code.push(PUSH4);
+		code.extend_from_slice(&transfer);
+		code.extend_from_slice(&[0u8; 27]);
+		assert!(scan_selectors(&code).is_empty());
+
+		code.push(PUSH4);
+		code.extend_from_slice(&transfer);
+		assert_eq!(scan_selectors(&code), HashSet::from([transfer]));
+	}
+
+	#[test]
+	fn test_scan_selectors_pads_short_pushes() {
+		// ERC-1155 `balanceOf(address,uint256)` is 0x00fdd58e, pushed by solc as PUSH3 0xfdd58e
+		let balance_of = selector("balanceOf(address,uint256)");
+		assert_eq!(balance_of, [0x00, 0xfd, 0xd5, 0x8e]);
+
+		let code = [PUSH3, 0xfd, 0xd5, 0x8e];
+		assert_eq!(scan_selectors(&code), HashSet::from([balance_of]));
+		// A truncated operand at the end of the code is ignored
+		assert!(scan_selectors(&code[..3]).is_empty());
+	}
+
+	#[test]
+	fn test_missing_functions() {
+		let abi = ethers::abi::parse_abi(&[
+			"function transfer(address to, uint256 amount) returns (bool)",
+			"function mint(address to, uint256 amount)",
+		])
+		.unwrap();
+
+		let code = dispatcher(&["transfer(address,uint256)"]);
+		assert_eq!(missing_functions(&code, &abi), vec!["mint(address,uint256)".to_string()]);
+
+		let code = dispatcher(&["transfer(address,uint256)", "mint(address,uint256)"]);
+		assert!(missing_functions(&code, &abi).is_empty());
+	}
 }
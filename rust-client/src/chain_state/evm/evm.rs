@@ -1,33 +1,92 @@
-use super::contracts::{erc20::ERC20TransactionFetcher, types::ContractDetector};
-use crate::models::{
-	block::{EvmBlock, LatestBlock},
-	extrinsic::{OtherTx, TransactionDetail},
-	gas::BlockGas,
+use super::{
+	batch::BatchClient,
+	contracts::{
+		decoder::AbiDecoder,
+		erc1155::ERC1155MetadataFetcher,
+		erc20::ERC20TransactionFetcher,
+		erc721::ERC721MetadataFetcher,
+		logs::{decode_token_events, to_evm_log},
+		types::ContractDetector,
+	},
+	revert::RevertReasonFetcher,
+	trace::trace_block_calls,
+};
+use crate::{
+	models::{
+		block::{EvmBlock, LatestBlock},
+		contract::Erc20Info,
+		extrinsic::{AccessListEntry, ContractCreation, OtherTx, TransactionDetail},
+		gas::BlockGas,
+	},
+	utils::{is_unsupported_method, split_transaction_fee},
 };
 
 use anyhow::{anyhow, Result};
-use futures::try_join;
-use std::{convert::TryFrom, str::FromStr};
+use serde_json::json;
+use std::{
+	collections::{HashMap, HashSet},
+	convert::TryFrom,
+	str::FromStr,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex, RwLock,
+	},
+};
 
 use ethers::{
-	abi::Address,
+	abi::{Abi, Address},
 	providers::{Http, Middleware, Provider},
-	types::{BlockId, Transaction, H160, H256},
+	types::{BlockId, BlockNumber, Bytes, Transaction, TransactionReceipt, H160, H256, U256},
+	utils::keccak256,
 };
 
 #[derive(Debug, Clone)]
 pub struct EvmClient {
 	pub provider: Provider<Http>,
+	// Registered contract ABIs, shared with the ERC-20 fetcher so a registration reaches both
+	pub decoder: Arc<RwLock<AbiDecoder>>,
 	pub contract_detector: ContractDetector,
 	pub erc20_fetcher: ERC20TransactionFetcher,
+	pub erc721_fetcher: ERC721MetadataFetcher,
+	pub erc1155_fetcher: ERC1155MetadataFetcher,
+	pub revert_fetcher: RevertReasonFetcher,
+	batch_client: BatchClient,
+	// Whether the node serves `eth_getBlockReceipts`; cleared once the node rejects the method
+	block_receipts_supported: Arc<AtomicBool>,
+	// Addresses known to hold code, so recurring contract recipients are only looked up once
+	code_cache: Arc<Mutex<ContractCache>>,
+	// Whether blocks are traced for internal transactions, which needs the debug namespace
+	trace_internal: bool,
 }
 
 impl EvmClient {
 	pub fn new(provider_url: &str) -> Result<Self> {
 		let provider = Provider::<Http>::try_from(provider_url.to_string())?;
-		let erc20_fetcher = ERC20TransactionFetcher::new(provider.clone());
+		let decoder = Arc::new(RwLock::new(AbiDecoder::new()));
+		let erc20_fetcher = ERC20TransactionFetcher::new(provider.clone(), decoder.clone());
+		let erc721_fetcher = ERC721MetadataFetcher::new(provider.clone());
+		let erc1155_fetcher = ERC1155MetadataFetcher::new(provider.clone());
 		let contract_detector = ContractDetector::new(provider.clone());
-		Ok(Self { provider, contract_detector, erc20_fetcher })
+		let revert_fetcher = RevertReasonFetcher::new(provider.clone());
+		Ok(Self {
+			provider,
+			decoder,
+			contract_detector,
+			erc20_fetcher,
+			erc721_fetcher,
+			erc1155_fetcher,
+			revert_fetcher,
+			batch_client: BatchClient::new(provider_url),
+			block_receipts_supported: Arc::new(AtomicBool::new(true)),
+			code_cache: Arc::new(Mutex::new(ContractCache::new(CONTRACT_CACHE_CAPACITY))),
+			trace_internal: false,
+		})
+	}
+
+	/// Trace every block with `debug_traceBlockByNumber` to collect internal transactions
+	pub fn with_internal_tracing(mut self, enabled: bool) -> Self {
+		self.trace_internal = enabled;
+		self
 	}
 
 	pub async fn get_lastet_block(&self) -> Result<Option<LatestBlock>> {
@@ -46,34 +105,106 @@ impl EvmClient {
 
 	pub async fn get_block(&self, block_number: u64) -> Result<Option<EvmBlock>> {
 		let block_id = BlockId::from(block_number);
-		if let Some(block) = self.provider.get_block(block_id).await? {
-			let mut block_transactions = Vec::new();
+		if let Some(block) = self.provider.get_block_with_txs(block_id).await? {
+			let receipts = self.get_block_receipts(block_number, &block.transactions).await?;
 
-			for transaction_hash in block.transactions {
-				let (tx_detail, receipt) = try_join!(
-					self.provider.get_transaction(transaction_hash),
-					self.provider.get_transaction_receipt(transaction_hash),
-				)
-				.map_err(|e| anyhow!("Error fetching transaction data: {:?}", e))?;
+			// Successful deployments in this block make their address a contract from now on
+			for receipt in receipts.values() {
+				let deployed = receipt.status.map_or(false, |status| status.as_u32() == 1);
+				if let Some(contract_address) = receipt.contract_address.filter(|_| deployed) {
+					self.code_cache.lock().unwrap().insert(contract_address);
+				}
+			}
 
-				if let Some((tx, res)) = tx_detail.zip(receipt) {
-					let is_contract =
-						self.is_contract(tx.to.unwrap_or_default(), Some(block_id)).await?;
+			let recipients: HashSet<H160> =
+				block.transactions.iter().filter_map(|tx| tx.to).collect();
+			let contracts = self.get_contract_presence(recipients, block_id).await?;
+			let bytecode_hashes = self.get_bytecode_hashes(&receipts, block_id).await?;
+			let base_fee_per_gas = block.base_fee_per_gas.unwrap_or_default();
+			let mut internal_transactions = if self.trace_internal {
+				trace_block_calls(&self.provider, block_number).await?.into_iter()
+			} else {
+				Vec::new().into_iter()
+			};
 
+			let mut block_transactions = Vec::new();
+			for tx in block.transactions {
+				// Traces come in transaction order, so take one per transaction
+				let internal = internal_transactions.next().unwrap_or_default();
+				if let Some(res) = receipts.get(&tx.hash) {
+					let status = res.status.map_or(false, |status| status.as_u32() == 1);
+					let gas_used = res.gas_used.unwrap_or_default();
+					// Dynamic fee transactions pay less than their cap, so prefer the receipt
+					let effective_gas_price =
+						res.effective_gas_price.or(tx.gas_price).unwrap_or_default();
+					let (burnt_fee, priority_fee) =
+						split_transaction_fee(gas_used, effective_gas_price, base_fee_per_gas);
+					// Receipts carry no revert data, so failed transactions are replayed
+					let revert_data = if status {
+						None
+					} else {
+						self.revert_fetcher
+							.get_revert_data(&tx)
+							.await
+							.map(|data| format!("0x{}", hex::encode(data)))
+					};
 					let transaction_detail = TransactionDetail {
 						transaction_hash: format!("0x{}", hex::encode(tx.hash.as_bytes())),
-						status: res.status.map_or(false, |status| status.as_u32() == 1),
+						status,
 						from: format!("0x{}", hex::encode(tx.from.as_bytes())),
-						to: format!("0x{}", hex::encode(tx.to.unwrap_or_default().as_bytes())),
-						is_contract,
-						amount: tx.value.as_u128(),
-						gas_price: tx.gas_price.unwrap_or_default().as_u128(),
-						gas_use: res.gas_used.unwrap_or_default().as_u64(),
+						to: tx
+							.to
+							.map(|to| format!("0x{}", hex::encode(to.as_bytes())))
+							.unwrap_or_default(),
+						is_contract: tx
+							.to
+							.and_then(|to| contracts.get(&to).copied())
+							.unwrap_or_default(),
+						// Reverted deployments still report an address but leave no code there
+						contract_creation: res
+							.contract_address
+							.filter(|_| tx.to.is_none() && status)
+							.map(|address| ContractCreation {
+								address: format!("0x{}", hex::encode(address.as_bytes())),
+								bytecode_hash: bytecode_hashes
+									.get(&address)
+									.cloned()
+									.unwrap_or_default(),
+							}),
+						amount: tx.value.to_string(),
+						gas_price: tx.gas_price.unwrap_or_default().to_string(),
+						gas_use: gas_used.low_u64(),
+						fee: gas_used.full_mul(effective_gas_price).to_string(),
+						max_fee_per_gas: tx.max_fee_per_gas.map(|fee| fee.to_string()),
+						max_priority_fee_per_gas: tx
+							.max_priority_fee_per_gas
+							.map(|fee| fee.to_string()),
+						effective_gas_price: effective_gas_price.to_string(),
+						burnt_fee: burnt_fee.to_string(),
+						priority_fee: priority_fee.to_string(),
+						access_list: tx
+							.access_list
+							.iter()
+							.flat_map(|access_list| access_list.0.iter())
+							.map(|item| AccessListEntry {
+								address: format!("0x{}", hex::encode(item.address.as_bytes())),
+								storage_keys: item
+									.storage_keys
+									.iter()
+									.map(|key| format!("0x{}", hex::encode(key.as_bytes())))
+									.collect(),
+							})
+							.collect(),
+						input: format!("0x{}", hex::encode(&tx.input)),
+						revert_data,
 						other: OtherTx {
 							transation_type: res.transaction_type.unwrap_or_default().as_u32(),
 							nonce: tx.nonce.as_u64(),
 							index: res.transaction_index.as_u32(),
 						},
+						logs: res.logs.iter().map(to_evm_log).collect(),
+						token_transfers: res.logs.iter().flat_map(decode_token_events).collect(),
+						internal_transactions: internal,
 					};
 					block_transactions.push(transaction_detail);
 				}
@@ -89,7 +220,8 @@ impl EvmClient {
 				gas: BlockGas {
 					gas_used: block.gas_used.as_u64(),
 					gas_limit: block.gas_limit.as_u64(),
-					base_fee_per_gas: block.base_fee_per_gas.unwrap_or_default().as_u64(),
+					base_fee_per_gas: base_fee_per_gas.to_string(),
+					burnt_fees: base_fee_per_gas.full_mul(block.gas_used).to_string(),
 				},
 			}))
 		} else {
@@ -97,16 +229,77 @@ impl EvmClient {
 		}
 	}
 
-	pub async fn check_balance(&self, address: &str, block: Option<BlockId>) -> Result<u128> {
+	pub async fn check_balance(&self, address: &str, block: Option<BlockId>) -> Result<U256> {
 		let address = Address::from_str(address)?;
 		let balance = self.provider.get_balance(address, block).await?;
 
-		Ok(balance.as_u128())
+		Ok(balance)
+	}
+
+	/// Number of transactions sent by `address`, i.e. its next nonce
+	pub async fn get_nonce(&self, address: &str) -> Result<u64> {
+		let address = Address::from_str(address)?;
+		let nonce = self.provider.get_transaction_count(address, None).await?;
+
+		Ok(nonce.low_u64())
 	}
 
 	pub async fn is_contract(&self, address: H160, block_number: Option<BlockId>) -> Result<bool> {
+		if self.code_cache.lock().unwrap().contains(&address) {
+			return Ok(true);
+		}
+
+		// Empty code is not cached, as a CREATE2 deployment may still put code there
 		let code = self.provider.get_code(address, block_number).await?;
-		Ok(!code.0.is_empty())
+		let is_contract = !code.0.is_empty();
+		if is_contract {
+			self.code_cache.lock().unwrap().insert(address);
+		}
+		Ok(is_contract)
+	}
+
+	pub async fn get_token_info(&self, token_address: &str) -> Result<Erc20Info> {
+		self.erc20_fetcher.get_token_info(Address::from_str(token_address)?).await
+	}
+
+	/// Current `totalSupply` of an ERC-20 token in decimal
+	pub async fn get_token_total_supply(&self, token_address: &str) -> Result<String> {
+		let total_supply =
+			self.erc20_fetcher.get_total_supply(Address::from_str(token_address)?).await?;
+		Ok(total_supply.to_string())
+	}
+
+	/// Signatures of the functions in `abi` that the contract at `address` does not implement,
+	/// `None` when no contract is deployed there
+	pub async fn unmatched_abi_functions(
+		&self,
+		address: &str,
+		abi: &Abi,
+	) -> Result<Option<Vec<String>>> {
+		let address = Address::from_str(address)?;
+		Ok(self.contract_detector.unmatched_functions(address, abi).await?)
+	}
+
+	/// `tokenURI` of an ERC-721 token given as addresses and ids appear in decoded transfers
+	pub async fn get_nft_token_uri(
+		&self,
+		contract: &str,
+		token_id: &str,
+	) -> Result<Option<String>> {
+		let contract = Address::from_str(contract)?;
+		let token_id = U256::from_dec_str(token_id)?;
+		self.erc721_fetcher.get_token_uri(contract, token_id).await
+	}
+
+	/// ERC-1155 `uri` of a token id, with `{id}` already substituted
+	pub async fn get_multi_token_uri(
+		&self,
+		contract: &str,
+		token_id: &str,
+	) -> Result<Option<String>> {
+		let contract = Address::from_str(contract)?;
+		let token_id = U256::from_dec_str(token_id)?;
+		self.erc1155_fetcher.get_token_uri(contract, token_id).await
 	}
 
 	pub async fn get_transaction_by_hash(&self, tx_hash: &str) -> Result<Option<Transaction>> {
@@ -115,11 +308,166 @@ impl EvmClient {
 		Ok(transaction)
 	}
 
-	pub fn addree_from_string(&self, address: String) -> Result<H160> {
-		H160::from_str(&address).map_err(|e| anyhow!("Error: {:?}", e))
+	/// Receipts keyed by transaction hash, from `eth_getBlockReceipts` when the node serves it
+	/// and otherwise from one batched `eth_getTransactionReceipt` call.
+	async fn get_block_receipts(
+		&self,
+		block_number: u64,
+		transactions: &[Transaction],
+	) -> Result<HashMap<H256, TransactionReceipt>> {
+		if transactions.is_empty() {
+			return Ok(HashMap::new());
+		}
+
+		if self.block_receipts_supported.load(Ordering::Relaxed) {
+			match self.provider.get_block_receipts(BlockNumber::from(block_number)).await {
+				Ok(receipts) if receipts.len() == transactions.len() =>
+					return Ok(receipts
+						.into_iter()
+						.map(|receipt| (receipt.transaction_hash, receipt))
+						.collect()),
+				Ok(_) => {},
+				Err(e) if is_unsupported_method(&e) =>
+					self.block_receipts_supported.store(false, Ordering::Relaxed),
+				// Anything else, e.g. a timeout, falls back for this block only
+				Err(_) => {},
+			}
+		}
+
+		let params = transactions.iter().map(|tx| json!([tx.hash])).collect();
+		let receipts: Vec<Option<TransactionReceipt>> = self
+			.batch_client
+			.request("eth_getTransactionReceipt", params)
+			.await
+			.map_err(|e| anyhow!("Error fetching transaction receipts: {:?}", e))?;
+
+		Ok(receipts
+			.into_iter()
+			.flatten()
+			.map(|receipt| (receipt.transaction_hash, receipt))
+			.collect())
+	}
+
+	/// Code presence for each address, fetching unknown ones in one batched `eth_getCode`.
+	async fn get_contract_presence(
+		&self,
+		addresses: HashSet<H160>,
+		block_id: BlockId,
+	) -> Result<HashMap<H160, bool>> {
+		let mut presence = HashMap::new();
+		let mut uncached = Vec::new();
+		{
+			let mut cache = self.code_cache.lock().unwrap();
+			for address in addresses {
+				if cache.contains(&address) {
+					presence.insert(address, true);
+				} else {
+					uncached.push(address);
+				}
+			}
+		}
+
+		let params = uncached.iter().map(|address| json!([address, block_id])).collect();
+		let codes: Vec<Bytes> = self
+			.batch_client
+			.request("eth_getCode", params)
+			.await
+			.map_err(|e| anyhow!("Error fetching contract code: {:?}", e))?;
+
+		let mut cache = self.code_cache.lock().unwrap();
+		for (address, code) in uncached.into_iter().zip(codes) {
+			let is_contract = !code.0.is_empty();
+			if is_contract {
+				cache.insert(address);
+			}
+			presence.insert(address, is_contract);
+		}
+
+		Ok(presence)
+	}
+
+	/// keccak256 of the code deployed by each creation receipt, in one batched `eth_getCode`.
+	async fn get_bytecode_hashes(
+		&self,
+		receipts: &HashMap<H256, TransactionReceipt>,
+		block_id: BlockId,
+	) -> Result<HashMap<H160, String>> {
+		let deployed: Vec<H160> =
+			receipts.values().filter_map(|receipt| receipt.contract_address).collect();
+
+		let params = deployed.iter().map(|address| json!([address, block_id])).collect();
+		let codes: Vec<Bytes> = self
+			.batch_client
+			.request("eth_getCode", params)
+			.await
+			.map_err(|e| anyhow!("Error fetching deployed code: {:?}", e))?;
+
+		Ok(deployed
+			.into_iter()
+			.zip(codes)
+			.map(|(address, code)| (address, format!("0x{}", hex::encode(keccak256(&code.0)))))
+			.collect())
 	}
 
 	pub fn address_zero(&self) -> String {
 		format!("0x{:0>40x}", Address::zero())
 	}
 }
+
+// Enough for the contracts a busy chain calls, at a few MB
+const CONTRACT_CACHE_CAPACITY: usize = 100_000;
+
+/// Bounded set of contract addresses that keeps two generations: when the current one is full
+/// it replaces the previous one, so only addresses unused for a whole generation are dropped.
+#[derive(Debug)]
+struct ContractCache {
+	capacity: usize,
+	current: HashSet<H160>,
+	previous: HashSet<H160>,
+}
+
+impl ContractCache {
+	fn new(capacity: usize) -> Self {
+		Self { capacity, current: HashSet::new(), previous: HashSet::new() }
+	}
+
+	fn contains(&mut self, address: &H160) -> bool {
+		if self.current.contains(address) {
+			return true;
+		}
+		// Recently used addresses move to the current generation
+		if self.previous.remove(address) {
+			self.insert(*address);
+			return true;
+		}
+		false
+	}
+
+	fn insert(&mut self, address: H160) {
+		if self.current.len() >= self.capacity {
+			self.previous = std::mem::take(&mut self.current);
+		}
+		self.current.insert(address);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_contract_cache_keeps_recent_addresses() {
+		let [a, b, c] = [1u64, 2, 3].map(H160::from_low_u64_be);
+		let mut cache = ContractCache::new(2);
+		cache.insert(a);
+		cache.insert(b);
+		// Full, so `a` and `b` become the previous generation
+		cache.insert(c);
+		assert!(cache.contains(&a));
+		// The next rotation drops `b`, which was not used in between
+		cache.insert(H160::from_low_u64_be(4));
+		assert!(!cache.contains(&b));
+		assert!(cache.contains(&a));
+		assert!(cache.contains(&c));
+	}
+}
@@ -0,0 +1,143 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Where token metadata is read from once a `tokenURI` or `uri` has been resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetadataSource {
+	Http(String),
+	Inline(Vec<u8>),
+}
+
+/// Loads the JSON metadata a token URI points at. Implement it to add caching, other
+/// gateways or schemes without touching the indexer.
+#[async_trait]
+pub trait MetadataFetcher: Send + Sync {
+	async fn fetch(&self, uri: &str) -> Result<Value>;
+}
+
+/// Fetches `http(s)://` URIs directly, `ipfs://` through a gateway and decodes `data:` URIs.
+#[derive(Debug, Clone)]
+pub struct HttpMetadataFetcher {
+	http: reqwest::Client,
+	ipfs_gateway: String,
+}
+
+impl HttpMetadataFetcher {
+	pub fn new(ipfs_gateway: &str) -> Result<Self> {
+		let http = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+		Ok(Self { http, ipfs_gateway: ipfs_gateway.trim_end_matches('/').to_string() })
+	}
+}
+
+#[async_trait]
+impl MetadataFetcher for HttpMetadataFetcher {
+	async fn fetch(&self, uri: &str) -> Result<Value> {
+		match resolve_metadata_uri(uri, &self.ipfs_gateway) {
+			Some(MetadataSource::Http(url)) => self
+				.http
+				.get(&url)
+				.send()
+				.await?
+				.error_for_status()?
+				.json()
+				.await
+				.map_err(|e| anyhow!("Invalid metadata at {}: {:?}", url, e)),
+			Some(MetadataSource::Inline(bytes)) => serde_json::from_slice(&bytes)
+				.map_err(|e| anyhow!("Invalid inline metadata: {:?}", e)),
+			None => Err(anyhow!("Unsupported metadata URI: {}", uri)),
+		}
+	}
+}
+
+/// Map a token URI onto a source, `None` for schemes we cannot read.
+pub fn resolve_metadata_uri(uri: &str, ipfs_gateway: &str) -> Option<MetadataSource> {
+	let uri = uri.trim();
+
+	if let Some(path) = uri.strip_prefix("ipfs://") {
+		// Some contracts repeat the namespace, as in `ipfs://ipfs/<cid>`
+		let path = path.strip_prefix("ipfs/").unwrap_or(path);
+		return Some(MetadataSource::Http(format!("{}/{}", ipfs_gateway, path)));
+	}
+
+	if let Some(data) = uri.strip_prefix("data:") {
+		let (header, payload) = data.split_once(',')?;
+		let bytes = if header.ends_with(";base64") {
+			STANDARD.decode(payload).ok()?
+		} else {
+			percent_decode(payload)
+		};
+		return Some(MetadataSource::Inline(bytes));
+	}
+
+	if uri.starts_with("https://") || uri.starts_with("http://") {
+		return Some(MetadataSource::Http(uri.to_string()));
+	}
+
+	None
+}
+
+fn percent_decode(input: &str) -> Vec<u8> {
+	let bytes = input.as_bytes();
+	let mut decoded = Vec::with_capacity(bytes.len());
+	let mut i = 0;
+	while i < bytes.len() {
+		let escaped = bytes.get(i + 1..i + 3).and_then(|hex| {
+			std::str::from_utf8(hex).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok())
+		});
+		match (bytes[i], escaped) {
+			(b'%', Some(byte)) => {
+				decoded.push(byte);
+				i += 3;
+			},
+			(byte, _) => {
+				decoded.push(byte);
+				i += 1;
+			},
+		}
+	}
+	decoded
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const GATEWAY: &str = "https://ipfs.io/ipfs";
+
+	#[test]
+	fn test_resolve_ipfs_uri() {
+		assert_eq!(
+			resolve_metadata_uri("ipfs://QmHash/1.json", GATEWAY),
+			Some(MetadataSource::Http("https://ipfs.io/ipfs/QmHash/1.json".to_string()))
+		);
+		assert_eq!(
+			resolve_metadata_uri("ipfs://ipfs/QmHash", GATEWAY),
+			Some(MetadataSource::Http("https://ipfs.io/ipfs/QmHash".to_string()))
+		);
+	}
+
+	#[test]
+	fn test_resolve_data_uri() {
+		let encoded = STANDARD.encode(r#"{"name":"Punk"}"#);
+		assert_eq!(
+			resolve_metadata_uri(&format!("data:application/json;base64,{}", encoded), GATEWAY),
+			Some(MetadataSource::Inline(br#"{"name":"Punk"}"#.to_vec()))
+		);
+		assert_eq!(
+			resolve_metadata_uri(r#"data:application/json,{"name":"A%20B"}"#, GATEWAY),
+			Some(MetadataSource::Inline(br#"{"name":"A B"}"#.to_vec()))
+		);
+	}
+
+	#[test]
+	fn test_resolve_http_and_unsupported_uri() {
+		assert_eq!(
+			resolve_metadata_uri("https://example.com/1", GATEWAY),
+			Some(MetadataSource::Http("https://example.com/1".to_string()))
+		);
+		assert_eq!(resolve_metadata_uri("ar://tx", GATEWAY), None);
+	}
+}
@@ -1,4 +1,8 @@
+pub mod batch;
 pub mod contracts;
 pub mod evm;
+pub mod metadata;
+pub mod revert;
+pub mod trace;
 
 pub use evm::EvmClient;
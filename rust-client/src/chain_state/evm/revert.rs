@@ -0,0 +1,77 @@
+use std::sync::{
+	atomic::{AtomicBool, Ordering},
+	Arc,
+};
+
+use ethers::{
+	providers::{Http, Middleware, Provider, ProviderError, RpcError},
+	types::{
+		transaction::eip2718::TypedTransaction, BlockId, Bytes, GethDebugBuiltInTracerType,
+		GethDebugTracerType, GethDebugTracingOptions, GethTrace, GethTraceFrame, Transaction,
+	},
+};
+
+use crate::utils::is_unsupported_method;
+
+/// Recovers the revert data of failed transactions, which receipts do not carry.
+///
+/// `debug_traceTransaction` replays the transaction in its exact position; nodes without the
+/// debug namespace fall back to an `eth_call` against the state of the parent block.
+#[derive(Debug, Clone)]
+pub struct RevertReasonFetcher {
+	provider: Provider<Http>,
+	// Whether the node serves `debug_traceTransaction`; cleared once the node rejects the method
+	debug_trace_supported: Arc<AtomicBool>,
+}
+
+impl RevertReasonFetcher {
+	pub fn new(provider: Provider<Http>) -> Self {
+		Self { provider, debug_trace_supported: Arc::new(AtomicBool::new(true)) }
+	}
+
+	/// Revert data of a failed transaction, `None` when the node reports none
+	pub async fn get_revert_data(&self, transaction: &Transaction) -> Option<Bytes> {
+		if self.debug_trace_supported.load(Ordering::Relaxed) {
+			match self.trace_revert_data(transaction).await {
+				Ok(data) => return data,
+				Err(e) if is_unsupported_method(&e) =>
+					self.debug_trace_supported.store(false, Ordering::Relaxed),
+				// Anything else, e.g. a timeout, falls back for this transaction only
+				Err(_) => {},
+			}
+		}
+		self.call_revert_data(transaction).await
+	}
+
+	async fn trace_revert_data(
+		&self,
+		transaction: &Transaction,
+	) -> Result<Option<Bytes>, ProviderError> {
+		let options = GethDebugTracingOptions {
+			tracer: Some(GethDebugTracerType::BuiltInTracer(
+				GethDebugBuiltInTracerType::CallTracer,
+			)),
+			..Default::default()
+		};
+		let trace = self.provider.debug_trace_transaction(transaction.hash, options).await?;
+		match trace {
+			GethTrace::Known(GethTraceFrame::CallTracer(frame)) =>
+				Ok(frame.output.filter(|output| !output.is_empty())),
+			_ => Ok(None),
+		}
+	}
+
+	async fn call_revert_data(&self, transaction: &Transaction) -> Option<Bytes> {
+		let parent = transaction.block_number?.as_u64().checked_sub(1)?;
+		let call = TypedTransaction::from(transaction);
+
+		// A call that succeeds against the parent state leaves nothing to decode
+		match self.provider.call(&call, Some(BlockId::from(parent))).await {
+			Ok(_) => None,
+			Err(e) => e
+				.as_error_response()
+				.and_then(|response| response.as_revert_data())
+				.filter(|data| !data.is_empty()),
+		}
+	}
+}
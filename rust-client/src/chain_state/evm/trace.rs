@@ -0,0 +1,105 @@
+use anyhow::{anyhow, Result};
+
+use ethers::{
+	providers::{Http, Middleware, Provider},
+	types::{
+		BlockNumber, CallFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
+		GethDebugTracingOptions, GethTrace, GethTraceFrame,
+	},
+};
+
+use crate::models::extrinsic::InternalTransaction;
+
+/// Internal calls of every transaction in a block, in transaction order.
+///
+/// Uses `debug_traceBlockByNumber` with the built-in `callTracer`, so the node must expose the
+/// debug namespace.
+pub async fn trace_block_calls(
+	provider: &Provider<Http>,
+	block_number: u64,
+) -> Result<Vec<Vec<InternalTransaction>>> {
+	let options = GethDebugTracingOptions {
+		tracer: Some(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer)),
+		..Default::default()
+	};
+	let traces = provider
+		.debug_trace_block_by_number(Some(BlockNumber::from(block_number)), options)
+		.await
+		.map_err(|e| anyhow!("Error tracing block {}: {:?}", block_number, e))?;
+
+	Ok(traces
+		.into_iter()
+		.map(|trace| match trace {
+			GethTrace::Known(GethTraceFrame::CallTracer(frame)) => internal_calls(&frame),
+			_ => Vec::new(),
+		})
+		.collect())
+}
+
+/// Nested calls of a transaction's root frame, depth first; the root call itself is the
+/// transaction and is not included.
+pub fn internal_calls(root: &CallFrame) -> Vec<InternalTransaction> {
+	let mut calls = Vec::new();
+	collect_calls(root, 1, &mut calls);
+	calls
+}
+
+fn collect_calls(frame: &CallFrame, depth: u32, calls: &mut Vec<InternalTransaction>) {
+	for call in frame.calls.iter().flatten() {
+		calls.push(InternalTransaction {
+			index: calls.len() as u32,
+			call_type: call.typ.clone(),
+			from: format!("0x{}", hex::encode(call.from.as_bytes())),
+			to: call
+				.to
+				.as_ref()
+				.and_then(|to| to.as_address())
+				.map(|to| format!("0x{}", hex::encode(to.as_bytes())))
+				.unwrap_or_default(),
+			value: call.value.unwrap_or_default().to_string(),
+			depth,
+			error: call.error.clone(),
+		});
+		collect_calls(call, depth + 1, calls);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethers::types::{Address, Bytes, NameOrAddress, U256};
+
+	fn frame(typ: &str, to: u8, value: u64, calls: Vec<CallFrame>) -> CallFrame {
+		CallFrame {
+			typ: typ.to_string(),
+			from: Address::repeat_byte(0x01),
+			to: Some(NameOrAddress::Address(Address::repeat_byte(to))),
+			value: Some(U256::from(value)),
+			gas: U256::zero(),
+			gas_used: U256::zero(),
+			input: Bytes::default(),
+			output: None,
+			error: None,
+			calls: Some(calls),
+			logs: None,
+		}
+	}
+
+	#[test]
+	fn test_internal_calls_are_flattened_depth_first() {
+		let nested = frame("CALL", 0x04, 5, Vec::new());
+		let root = frame(
+			"CALL",
+			0x02,
+			10,
+			vec![frame("DELEGATECALL", 0x03, 0, vec![nested]), frame("CALL", 0x05, 7, Vec::new())],
+		);
+
+		let calls = internal_calls(&root);
+		assert_eq!(calls.len(), 3);
+		assert_eq!((calls[0].call_type.as_str(), calls[0].depth), ("DELEGATECALL", 1));
+		assert_eq!((calls[1].value.as_str(), calls[1].depth), ("5", 2));
+		assert_eq!(calls[1].to, format!("0x{}", "04".repeat(20)));
+		assert_eq!((calls[2].index, calls[2].depth), (2, 1));
+	}
+}
@@ -1,11 +1,18 @@
-use crate::models::{
-	account::SubstrateAccount,
-	block::{BlockDetail, SubstrateRuntimeVersion},
-	event::{BlockEvent, EventDetail, StakingSlash, SubstrateEventRecord, TransferEvent},
-	extrinsic::{BlockExtrinsic, ExtrinsicDetail, ProcessExtrinsic},
-	identity::SubstrateIdentity,
-	staking::{EraStaking, ValidatorDetail},
-	Balance, MaxAdditionalFields, MaxJudgements,
+use crate::{
+	address::{encode_ss58, evm_to_substrate_address},
+	models::{
+		account::SubstrateAccount,
+		block::{BlockDetail, SubstrateRuntimeVersion},
+		event::{
+			BlockEvent, EventDetail, IdentityEvent, NewAccountEvent, NewSessionEvent, OffenceEvent,
+			StakingSlash, SubstrateEventRecord, TransferEvent,
+		},
+		extrinsic::{BlockExtrinsic, ExtrinsicDetail, ProcessExtrinsic},
+		identity::SubstrateIdentity,
+		session::SessionValidators,
+		staking::{EraStaking, StakingRole, ValidatorDetail},
+		Balance, MaxAdditionalFields, MaxJudgements,
+	},
 };
 
 use anyhow::{anyhow, Ok, Result};
@@ -22,21 +29,44 @@ use substrate_api_client::{
 };
 
 use selendra_primitives::{Hash, Nonce, Signature};
-use selendra_runtime::{Address, RuntimeCall, RuntimeEvent, SignedExtra};
+use selendra_runtime::{Address, RuntimeCall, RuntimeEvent, SessionKeys, SignedExtra};
 
+use frame_support::traits::GetCallMetadata;
 use frame_system::Phase;
-use sp_core::{blake2_256, crypto::Ss58Codec, storage::StorageKey};
+use sp_core::{blake2_256, crypto::Ss58Codec, storage::StorageKey, H160};
 use sp_runtime::{
 	generic::{Block, Header, UncheckedExtrinsic},
 	traits::BlakeTwo256,
-	AccountId32, OpaqueExtrinsic,
+	AccountId32, ConsensusEngineId, DigestItem, OpaqueExtrinsic,
 };
 use sp_staking::PagedExposureMetadata;
+use std::{collections::HashMap, str::FromStr};
 
 use pallet_balances::AccountData;
 use pallet_identity::{legacy::IdentityInfo, Data, Judgement, Registration};
 use pallet_staking::{ActiveEraInfo, EraRewardPoints, ValidatorPrefs};
 
+const AURA_ENGINE_ID: ConsensusEngineId = *b"aura";
+const EVM_ACCOUNTS_PALLET: &str = "EvmAccounts";
+const BABE_ENGINE_ID: ConsensusEngineId = *b"BABE";
+
+// Leading fields of `StakingLedger`, which is generic over the runtime
+#[derive(Debug, Decode)]
+pub struct StakingLedgerHead {
+	pub stash: AccountId32,
+	#[codec(compact)]
+	pub total: Balance,
+	#[codec(compact)]
+	pub active: Balance,
+}
+
+#[derive(Debug, Decode)]
+pub struct Nominations {
+	pub targets: Vec<AccountId32>,
+	pub submitted_in: u32,
+	pub suppressed: bool,
+}
+
 #[derive(Debug, Decode)]
 pub struct AccountInfo<Index, Balance> {
 	pub nonce: Index,
@@ -84,15 +114,18 @@ impl SubstrateClient {
 		.map_err(|e| anyhow!("Error fetching block data: {:?}", e))?;
 
 		if let Some(block) = block {
-			let block_extrinsics = self.process_extrinsics(&block, block_hash).await?;
 			let events = self
 				.api
 				.get_storage::<Vec<SubstrateEventRecord>>("System", "Events", block_hash)
 				.await
 				.map_err(|e| anyhow!("Error fetching events: {:?}", e))?
 				.unwrap_or_default();
+			let block_extrinsics = self
+				.process_extrinsics(&block, block_hash, &self.extrinsic_errors(&events))
+				.await?;
 
 			let block_events = self.process_event(events)?;
+			let author = self.get_block_author(&block.header).await?;
 			let block_data = BlockDetail {
 				block_number: block.header.number,
 				block_hash: format!("0x{}", hex::encode(block.header.hash())),
@@ -101,6 +134,7 @@ impl SubstrateClient {
 				activ_era: era_info.map_or(0, |era| era.index),
 				session_index: session_index.unwrap_or_default(),
 				state_root: format!("0x{}", hex::encode(block.header.state_root)),
+				author,
 				runtime_version: self.build_runtime_version(&runtime_version),
 				events: BlockEvent {
 					total: event_count.unwrap_or_default(),
@@ -172,7 +206,7 @@ impl SubstrateClient {
 
                     let info = validator_info.unwrap_or(PagedExposureMetadata { total: 0, own: 0, nominator_count: 0, page_count: 0 });
                     Ok(ValidatorDetail {
-                        account: account_id.to_ss58check(),
+                        account: encode_ss58(&account_id),
                         validators_commission: validator_commission.map_or("0%".to_string(), |vc| format!("{:?}", vc.commission)),
                         nominator_count: info.nominator_count,
                         total_staking: info.total,
@@ -200,6 +234,42 @@ impl SubstrateClient {
 		})
 	}
 
+	pub async fn get_session_validators(
+		&self,
+		block_number: Option<u32>,
+	) -> Result<SessionValidators> {
+		let block_hash = if let Some(block_number) = block_number {
+			self.get_block_hash(block_number).await?
+		} else {
+			None
+		};
+
+		let (session_index, validators, queued_keys) = try_join!(
+			self.api.get_storage::<u32>("Session", "CurrentIndex", block_hash),
+			self.api.get_storage::<Vec<AccountId32>>("Session", "Validators", block_hash),
+			self.api.get_storage::<Vec<(AccountId32, SessionKeys)>>(
+				"Session",
+				"QueuedKeys",
+				block_hash
+			),
+		)
+		.map_err(|e| anyhow!("Error fetching session validators: {:?}", e))?;
+
+		Ok(SessionValidators {
+			session_index: session_index.unwrap_or_default(),
+			validators: validators
+				.unwrap_or_default()
+				.into_iter()
+				.map(|account| encode_ss58(&account))
+				.collect(),
+			queued_validators: queued_keys
+				.unwrap_or_default()
+				.into_iter()
+				.map(|(account, _)| encode_ss58(&account))
+				.collect(),
+		})
+	}
+
 	pub async fn get_account_identity(&self, address: &str) -> Result<Option<SubstrateIdentity>> {
 		let account_id = self.convert_ss58_to_account_id32(address)?;
 		let identity_info = self.api
@@ -239,6 +309,84 @@ impl SubstrateClient {
 		}
 	}
 
+	/// Stash, controller, bonded amounts and validator or nominator intent of the bond an
+	/// account takes part in, either as its stash or as its controller
+	pub async fn get_staking_role(&self, address: &str) -> Result<StakingRole> {
+		let account_id = self.convert_ss58_to_account_id32(address)?;
+
+		let (controller, own_ledger) = try_join!(
+			self.api.get_storage_map::<AccountId32, AccountId32>(
+				"Staking",
+				"Bonded",
+				account_id.clone(),
+				None
+			),
+			self.api.get_storage_map::<AccountId32, StakingLedgerHead>(
+				"Staking",
+				"Ledger",
+				account_id.clone(),
+				None
+			),
+		)
+		.map_err(|e| anyhow!("Error fetching staking ledger: {:?}", e))?;
+
+		// The ledger is keyed by controller, which may differ from the stash
+		let ledger = match &controller {
+			Some(controller) if *controller != account_id => self
+				.api
+				.get_storage_map::<AccountId32, StakingLedgerHead>(
+					"Staking",
+					"Ledger",
+					controller.clone(),
+					None,
+				)
+				.await
+				.map_err(|e| anyhow!("Error fetching staking ledger: {:?}", e))?,
+			_ => own_ledger,
+		};
+		let stash = match (&controller, &ledger) {
+			(Some(_), _) => Some(account_id.clone()),
+			(None, Some(ledger)) => Some(ledger.stash.clone()),
+			(None, None) => None,
+		};
+		let controller = match (controller, &stash) {
+			(Some(controller), _) => Some(controller),
+			(None, Some(_)) => Some(account_id),
+			(None, None) => None,
+		};
+
+		// Validator and nominator intents are keyed by stash
+		let (validator_prefs, nominations) = match &stash {
+			Some(stash) => try_join!(
+				self.api.get_storage_map::<AccountId32, ValidatorPrefs>(
+					"Staking",
+					"Validators",
+					stash.clone(),
+					None
+				),
+				self.api.get_storage_map::<AccountId32, Nominations>(
+					"Staking",
+					"Nominators",
+					stash.clone(),
+					None
+				),
+			)
+			.map_err(|e| anyhow!("Error fetching staking intent: {:?}", e))?,
+			None => (None, None),
+		};
+
+		Ok(StakingRole {
+			stash: stash.as_ref().map(encode_ss58),
+			controller: controller.as_ref().map(encode_ss58),
+			is_validator: validator_prefs.is_some(),
+			nominations: nominations
+				.map(|nominations| nominations.targets.iter().map(encode_ss58).collect())
+				.unwrap_or_default(),
+			bonded: ledger.as_ref().map_or(0, |ledger| ledger.total),
+			active: ledger.as_ref().map_or(0, |ledger| ledger.active),
+		})
+	}
+
 	pub async fn get_accounts(
 		&self,
 		query_size: u32,
@@ -261,7 +409,7 @@ impl SubstrateClient {
 			.map(|key| {
 				let account_id = AccountId32::decode(&mut &key.0[48..])
 					.map_err(|_| anyhow!("Failed to decode account ID"))?;
-				Ok(account_id.to_ss58check())
+				Ok(encode_ss58(&account_id))
 			})
 			.collect()
 	}
@@ -301,7 +449,7 @@ impl SubstrateClient {
 			None
 		};
 
-		let account_id: AccountId32 = AccountId32::from_ss58check(account_ss58)?;
+		let account_id = self.convert_ss58_to_account_id32(account_ss58)?;
 
 		let account_data = self
 			.api
@@ -319,20 +467,82 @@ impl SubstrateClient {
 				free: account_data.data.free,
 				reserved: account_data.data.reserved,
 				lock: account_data.data.frozen,
+				nonce: account_data.nonce,
 			}))
 		} else {
 			Ok(None)
 		}
 	}
 
-	/// Convert SS58 address to EVM address (H160)
-	pub fn ss58_to_evm(&self, ss58_address: &str) -> Result<String> {
-		let account = AccountId32::from_ss58check(ss58_address)?;
+	pub async fn get_finalized_block_number(&self) -> Result<u32> {
+		let finalized_hash = self
+			.api
+			.get_finalized_head()
+			.await
+			.map_err(|e| anyhow!("Error fetching finalized head: {:?}", e))?;
+
+		self.api
+			.get_header(finalized_hash)
+			.await
+			.map_err(|e| anyhow!("Error fetching finalized header: {:?}", e))?
+			.map(|header| header.number)
+			.ok_or_else(|| anyhow!("Finalized header not found"))
+	}
+
+	/// EVM address claimed by a Substrate account.
+	///
+	/// The runtime maps EVM addresses to accounts by hashing, which cannot be reversed, so only
+	/// accounts with a claim in the EVM accounts pallet have an EVM address.
+	pub async fn ss58_to_evm(&self, ss58_address: &str) -> Result<Option<String>> {
+		if !self.has_evm_accounts_pallet() {
+			return Ok(None);
+		}
+		let account_id = self.convert_ss58_to_account_id32(ss58_address)?;
+
+		let evm_address = self
+			.api
+			.get_storage_map::<AccountId32, H160>(
+				EVM_ACCOUNTS_PALLET,
+				"EvmAddresses",
+				account_id,
+				None,
+			)
+			.await
+			.map_err(|e| anyhow!("Error fetching claimed EVM address: {:?}", e))?;
+
+		Ok(evm_address.map(|address| format!("0x{}", hex::encode(address.as_bytes()))))
+	}
+
+	/// Substrate account of an EVM address: the claimed account when there is one, otherwise
+	/// the account derived by the runtime's hashed address mapping.
+	pub async fn evm_to_ss58(&self, evm_address: &str) -> Result<String> {
+		match self.get_claimed_account(evm_address).await? {
+			Some(account) => Ok(account),
+			None => evm_to_substrate_address(evm_address)
+				.ok_or_else(|| anyhow!("Invalid EVM address: {}", evm_address)),
+		}
+	}
+
+	/// Substrate account that claimed an EVM address in the EVM accounts pallet
+	pub async fn get_claimed_account(&self, evm_address: &str) -> Result<Option<String>> {
+		if !self.has_evm_accounts_pallet() {
+			return Ok(None);
+		}
+		let address = H160::from_str(evm_address)
+			.map_err(|e| anyhow!("Invalid EVM address {}: {:?}", evm_address, e))?;
 
-		let bytes: &[u8; 32] = <AccountId32 as AsRef<[u8; 32]>>::as_ref(&account);
+		let account = self
+			.api
+			.get_storage_map::<H160, AccountId32>(EVM_ACCOUNTS_PALLET, "Accounts", address, None)
+			.await
+			.map_err(|e| anyhow!("Error fetching claimed account: {:?}", e))?;
 
-		// Take first 20 bytes for EVM address
-		Ok(format!("0x{}", hex::encode(&bytes[..20])))
+		Ok(account.map(|account| encode_ss58(&account)))
+	}
+
+	// Claims only exist on runtimes that include the EVM accounts pallet
+	fn has_evm_accounts_pallet(&self) -> bool {
+		self.api.metadata().pallet_by_name(EVM_ACCOUNTS_PALLET).is_some()
 	}
 
 	async fn get_block_hash(&self, block_number: u32) -> Result<Option<Hash>> {
@@ -346,6 +556,7 @@ impl SubstrateClient {
 		&self,
 		block: &Block<Header<u32, BlakeTwo256>, OpaqueExtrinsic>,
 		block_hash: Option<Hash>,
+		errors: &HashMap<u32, String>,
 	) -> Result<Vec<ExtrinsicDetail>> {
 		let mut extrinsics = Vec::new();
 
@@ -361,18 +572,70 @@ impl SubstrateClient {
 			let is_signed = total_fee > 0;
 
 			let processed_extrinsic = self.process_extrinsic(extrinsic_byte)?;
+			let call = processed_extrinsic.function.get_call_metadata();
 			extrinsics.push(ExtrinsicDetail {
 				is_signed,
 				signer: processed_extrinsic.signer,
 				index: index as u8,
 				hash: extrinsic_hash,
 				fee: total_fee,
+				pallet: call.pallet_name.to_string(),
+				method: call.function_name.to_string(),
 				runtime_call: format!("{:?}", processed_extrinsic.function),
+				success: !errors.contains_key(&(index as u32)),
+				error: errors.get(&(index as u32)).cloned(),
 			});
 		}
 		Ok(extrinsics)
 	}
 
+	/// Dispatch errors of the extrinsics that failed, by extrinsic index
+	fn extrinsic_errors(&self, events: &[SubstrateEventRecord]) -> HashMap<u32, String> {
+		events
+			.iter()
+			.filter_map(|record| match (&record.phase, &record.event) {
+				(
+					Phase::ApplyExtrinsic(index),
+					RuntimeEvent::System(frame_system::Event::ExtrinsicFailed {
+						dispatch_error,
+						..
+					}),
+				) => Some((*index, format!("{:?}", dispatch_error))),
+				_ => None,
+			})
+			.collect()
+	}
+
+	/// Resolve the block author from the pre-runtime digest against the session validators
+	/// of the parent block, which is the authority set the block was produced under.
+	async fn get_block_author(&self, header: &Header<u32, BlakeTwo256>) -> Result<Option<String>> {
+		let validators = self
+			.api
+			.get_storage::<Vec<AccountId32>>("Session", "Validators", Some(header.parent_hash))
+			.await
+			.map_err(|e| anyhow!("Error fetching session validators: {:?}", e))?
+			.unwrap_or_default();
+
+		if validators.is_empty() {
+			return Ok(None);
+		}
+
+		let author_index = header.digest.logs.iter().find_map(|log| match log {
+			DigestItem::PreRuntime(engine_id, data) if *engine_id == AURA_ENGINE_ID =>
+				u64::decode(&mut &data[..])
+					.ok()
+					.map(|slot| (slot % validators.len() as u64) as usize),
+			// Every BABE pre-digest variant starts with the authority index after its tag byte
+			DigestItem::PreRuntime(engine_id, data) if *engine_id == BABE_ENGINE_ID => data
+				.get(1..)
+				.and_then(|mut bytes| u32::decode(&mut bytes).ok())
+				.map(|i| i as usize),
+			_ => None,
+		});
+
+		Ok(author_index.and_then(|index| validators.get(index)).map(encode_ss58))
+	}
+
 	fn process_extrinsic(&self, extrinsic_byte: Vec<u8>) -> Result<ProcessExtrinsic> {
 		let decoded_extrinsic: UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra> =
 			Decode::decode(&mut &extrinsic_byte[..])
@@ -380,7 +643,7 @@ impl SubstrateClient {
 		let signer_address = decoded_extrinsic
 			.signature
 			.as_ref()
-			.map(|(signer, _, _)| signer.to_ss58check())
+			.map(|(signer, _, _)| encode_ss58(signer))
 			.unwrap_or_default();
 
 		Ok(ProcessExtrinsic { signer: signer_address, function: decoded_extrinsic.function })
@@ -392,23 +655,45 @@ impl SubstrateClient {
 			.enumerate()
 			.map(|(index, event)| {
 				let phase = match event.phase {
-					Phase::ApplyExtrinsic(index) => index,
-					_ => 0,
+					Phase::ApplyExtrinsic(index) => Some(index),
+					_ => None,
 				};
+				let (pallet, method) = self.event_name(&event.event);
 				Ok(EventDetail {
 					index: index as u32,
 					extrinsic_id: phase,
+					pallet,
+					method,
 					event: format!("{:?}", event.event),
 					slash_event: self.process_slash_event(event.event.clone())?,
-					transfer_event: self.process_transfer_event(event.event)?,
+					transfer_event: self.process_transfer_event(event.event.clone())?,
+					identity_event: self.process_identity_event(event.event.clone())?,
+					new_session_event: self.process_new_session_event(event.event.clone())?,
+					offence_events: self.process_offence_event(event.event.clone())?,
+					new_account_event: self.process_new_account_event(event.event)?,
 				})
 			})
 			.collect()
 	}
 
+	/// Pallet and variant name of an event, looked up in the metadata from the pallet and
+	/// variant indices that start its encoding
+	fn event_name(&self, event: &RuntimeEvent) -> (String, String) {
+		let encoded = event.encode();
+		self.api
+			.metadata()
+			.pallet_by_index(encoded[0])
+			.and_then(|pallet| {
+				pallet
+					.event_variant_by_index(encoded[1])
+					.map(|variant| (pallet.name().to_string(), variant.name.clone()))
+			})
+			.unwrap_or_default()
+	}
+
 	fn process_slash_event(&self, event: RuntimeEvent) -> Result<Option<StakingSlash>> {
 		if let RuntimeEvent::Staking(pallet_staking::Event::Slashed { staker, amount }) = event {
-			Ok(Some(StakingSlash { staker: staker.to_ss58check(), amount }))
+			Ok(Some(StakingSlash { staker: encode_ss58(&staker), amount }))
 		} else {
 			Ok(None)
 		}
@@ -417,7 +702,112 @@ impl SubstrateClient {
 	fn process_transfer_event(&self, event: RuntimeEvent) -> Result<Option<TransferEvent>> {
 		if let RuntimeEvent::Balances(pallet_balances::Event::Transfer { from, to, amount }) = event
 		{
-			Ok(Some(TransferEvent { from: from.to_ss58check(), to: to.to_ss58check(), amount }))
+			Ok(Some(TransferEvent { from: encode_ss58(&from), to: encode_ss58(&to), amount }))
+		} else {
+			Ok(None)
+		}
+	}
+
+	fn process_identity_event(&self, event: RuntimeEvent) -> Result<Option<IdentityEvent>> {
+		let identity_event = match event {
+			RuntimeEvent::Identity(pallet_identity::Event::IdentitySet { who }) => IdentityEvent {
+				event: "IdentitySet".to_string(),
+				who: encode_ss58(&who),
+				registrar_index: None,
+			},
+			RuntimeEvent::Identity(pallet_identity::Event::IdentityCleared { who, .. }) =>
+				IdentityEvent {
+					event: "IdentityCleared".to_string(),
+					who: encode_ss58(&who),
+					registrar_index: None,
+				},
+			RuntimeEvent::Identity(pallet_identity::Event::IdentityKilled { who, .. }) =>
+				IdentityEvent {
+					event: "IdentityKilled".to_string(),
+					who: encode_ss58(&who),
+					registrar_index: None,
+				},
+			RuntimeEvent::Identity(pallet_identity::Event::JudgementGiven {
+				target,
+				registrar_index,
+			}) => IdentityEvent {
+				event: "JudgementGiven".to_string(),
+				who: encode_ss58(&target),
+				registrar_index: Some(registrar_index),
+			},
+			_ => return Ok(None),
+		};
+
+		Ok(Some(identity_event))
+	}
+
+	fn process_new_session_event(&self, event: RuntimeEvent) -> Result<Option<NewSessionEvent>> {
+		if let RuntimeEvent::Session(pallet_session::Event::NewSession { session_index }) = event {
+			Ok(Some(NewSessionEvent { session_index }))
+		} else {
+			Ok(None)
+		}
+	}
+
+	/// Staking slashes and chills, plus committee bans which is how Selendra punishes
+	/// validators in place of the offences pallet.
+	fn process_offence_event(&self, event: RuntimeEvent) -> Result<Vec<OffenceEvent>> {
+		// The runtime has no offences pallet, so there is no `Offences.Offence` event or
+		// `Offences::Reports` storage. Bans are decided by CommitteeManagement from block
+		// production and slashes are applied by Staking, neither of which names a reporter, so
+		// `reporter` stays empty until the runtime records one.
+		let offences = match event {
+			RuntimeEvent::Staking(pallet_staking::Event::Slashed { staker, amount }) =>
+				vec![OffenceEvent {
+					event: "Slashed".to_string(),
+					offender: encode_ss58(&staker),
+					reporter: None,
+					amount: Some(amount),
+					era: None,
+					details: None,
+				}],
+			RuntimeEvent::Staking(pallet_staking::Event::SlashReported {
+				validator,
+				fraction,
+				slash_era,
+			}) => vec![OffenceEvent {
+				event: "SlashReported".to_string(),
+				offender: encode_ss58(&validator),
+				reporter: None,
+				amount: None,
+				era: Some(slash_era),
+				details: Some(format!("{:?}", fraction)),
+			}],
+			RuntimeEvent::Staking(pallet_staking::Event::Chilled { stash }) => vec![OffenceEvent {
+				event: "Chilled".to_string(),
+				offender: encode_ss58(&stash),
+				reporter: None,
+				amount: None,
+				era: None,
+				details: None,
+			}],
+			RuntimeEvent::CommitteeManagement(
+				pallet_committee_management::Event::BanValidators(banned),
+			) => banned
+				.into_iter()
+				.map(|(validator, ban_info)| OffenceEvent {
+					event: "BanValidators".to_string(),
+					offender: encode_ss58(&validator),
+					reporter: None,
+					amount: None,
+					era: Some(ban_info.start),
+					details: Some(format!("{:?}", ban_info.reason)),
+				})
+				.collect(),
+			_ => Vec::new(),
+		};
+
+		Ok(offences)
+	}
+
+	fn process_new_account_event(&self, event: RuntimeEvent) -> Result<Option<NewAccountEvent>> {
+		if let RuntimeEvent::System(frame_system::Event::NewAccount { account }) = event {
+			Ok(Some(NewAccountEvent { account: encode_ss58(&account) }))
 		} else {
 			Ok(None)
 		}
@@ -436,7 +826,8 @@ impl SubstrateClient {
 	}
 
 	fn convert_ss58_to_account_id32(&self, ss58_address: &str) -> Result<AccountId32> {
-		AccountId32::from_ss58check(ss58_address)
+		AccountId32::from_ss58check_with_version(ss58_address)
+			.map(|(account_id, _)| account_id)
 			.map_err(|e| anyhow!("Error converting SS58 to AccountId32: {:?}", e))
 	}
 
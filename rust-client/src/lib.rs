@@ -1,3 +1,4 @@
+pub mod address;
 pub mod chain_state;
 pub mod models;
 pub mod utils;
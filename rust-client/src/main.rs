@@ -1,7 +1,7 @@
 use anyhow::Result;
 // use ethers::types::transaction;
 // use selendra_rust_client::SubstrateClient;
-use selendra_rust_client::EvmClient;
+use selendra_rust_client::{address::parse_evm_address, EvmClient};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -34,10 +34,13 @@ async fn main() -> Result<()> {
 
 	for transaction in transactions {
 		let address = transaction.to;
+		if address.is_empty() {
+			continue; // contract creation
+		}
 
 		let contract_type = evm_client
 			.contract_detector
-			.detect(evm_client.addree_from_string(address).unwrap())
+			.detect(parse_evm_address(&address).unwrap(), None)
 			.await;
 
 		println!("{:#?}", contract_type.unwrap());
@@ -5,4 +5,5 @@ pub struct SubstrateAccount {
 	pub free: u128,
 	pub reserved: u128,
 	pub lock: u128,
+	pub nonce: u32,
 }
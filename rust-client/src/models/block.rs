@@ -28,6 +28,7 @@ pub struct BlockDetail {
 	pub activ_era: u32,
 	pub session_index: u32,
 	pub state_root: String,
+	pub author: Option<String>,
 	pub runtime_version: SubstrateRuntimeVersion,
 	pub total_issuance: Balance,
 	pub events: BlockEvent,
@@ -10,8 +10,8 @@ pub struct ERC20Transaction {
 	pub hash: String,
 	pub block_number: u64,
 	pub status: bool,
-	pub native_value: u128,
-	pub token_value: u128,
+	pub native_value: String, // uint256 in decimal
+	pub token_value: String,  // uint256 in decimal
 	pub transaction_gas: TransactionGas,
 	pub token_info: Erc20Info,
 	pub decoded_data: HashMap<String, String>,
@@ -23,11 +23,53 @@ pub struct Erc20Info {
 	pub name: String,
 	pub symbol: String,
 	pub decimals: u8,
+	pub total_supply: String, // uint256 in decimal
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct TokenTransfer {
+	pub token_address: String,
+	pub event: String, // Transfer, Approval, TransferSingle or TransferBatch
+	pub from: String,  // owner for Approval
+	pub to: String,    // spender for Approval
+	pub value: String, // raw uint256 in decimal
+	pub token_id: Option<String>, // ERC-721 or ERC-1155 id
+	pub log_index: u64,
+	pub batch_index: Option<u32>, // position within an ERC-1155 TransferBatch
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ContractType {
 	ERC20,
 	ERC721,
+	ERC777,
+	ERC1155,
 	Unknown,
 }
+
+impl ContractType {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ContractType::ERC20 => "ERC20",
+			ContractType::ERC721 => "ERC721",
+			ContractType::ERC777 => "ERC777",
+			ContractType::ERC1155 => "ERC1155",
+			ContractType::Unknown => "Unknown",
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DecodedParam {
+	pub name: String,
+	pub kind: String,             // solidity type, e.g. uint256
+	pub value: serde_json::Value, // integers as decimal strings, bytes and addresses as 0x hex
+}
+
+/// A function call or event decoded against an ABI
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DecodedData {
+	pub name: String,
+	pub signature: String, // e.g. transfer(address,uint256)
+	pub params: Vec<DecodedParam>,
+}
@@ -17,10 +17,16 @@ pub struct SubstrateEventRecord {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct EventDetail {
 	pub index: u32,
-	pub extrinsic_id: u32,
+	pub extrinsic_id: Option<u32>, // None outside the apply extrinsic phase
+	pub pallet: String,
+	pub method: String,
 	pub event: String,
 	pub slash_event: Option<StakingSlash>,
 	pub transfer_event: Option<TransferEvent>,
+	pub identity_event: Option<IdentityEvent>,
+	pub new_session_event: Option<NewSessionEvent>,
+	pub offence_events: Vec<OffenceEvent>,
+	pub new_account_event: Option<NewAccountEvent>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -41,3 +47,30 @@ pub struct TransferEvent {
 	pub to: String,
 	pub amount: u128,
 }
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IdentityEvent {
+	pub event: String,
+	pub who: String,
+	pub registrar_index: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct NewSessionEvent {
+	pub session_index: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct OffenceEvent {
+	pub event: String,
+	pub offender: String,
+	pub reporter: Option<String>,
+	pub amount: Option<u128>,
+	pub era: Option<u32>,
+	pub details: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct NewAccountEvent {
+	pub account: String,
+}
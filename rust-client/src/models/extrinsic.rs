@@ -2,6 +2,8 @@ use selendra_runtime::RuntimeCall;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
+use super::contract::TokenTransfer;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BlockExtrinsic {
 	pub total: u32,
@@ -15,7 +17,11 @@ pub struct ExtrinsicDetail {
 	pub is_signed: bool,
 	pub hash: String,
 	pub fee: u128,
+	pub pallet: String,
+	pub method: String,
 	pub runtime_call: String,
+	pub success: bool,
+	pub error: Option<String>, // dispatch error of a failed extrinsic
 }
 
 pub struct ProcessExtrinsic {
@@ -28,12 +34,37 @@ pub struct TransactionDetail {
 	pub transaction_hash: String, // hash
 	pub status: bool,             // self.provider.get_transaction_receipt(hash).status
 	pub from: String,
-	pub to: String,
+	pub to: String, // empty for contract creations
 	pub is_contract: bool,
-	pub amount: u128, // value
-	pub gas_price: u128,
+	pub contract_creation: Option<ContractCreation>,
+	pub amount: String,                           // value, uint256 in decimal
+	pub gas_price: String,                        // uint256 in decimal
 	pub gas_use: u64, // self.provider.get_transaction_receipt(hash).gas_used
+	pub fee: String,  // gas_use * effective_gas_price in decimal
+	pub max_fee_per_gas: Option<String>, // EIP-1559 fee cap, uint256 in decimal
+	pub max_priority_fee_per_gas: Option<String>, // EIP-1559 tip cap, uint256 in decimal
+	pub effective_gas_price: String, // price actually paid per gas, from the receipt
+	pub burnt_fee: String, // gas_use * base_fee_per_gas in decimal
+	pub priority_fee: String, // fee paid to the block author in decimal
+	pub access_list: Vec<AccessListEntry>, // EIP-2930 access list
+	pub input: String, // call data, hex encoded
+	pub revert_data: Option<String>, // hex encoded revert data of failed transactions
 	pub other: OtherTx,
+	pub logs: Vec<EvmLog>,
+	pub token_transfers: Vec<TokenTransfer>,
+	pub internal_transactions: Vec<InternalTransaction>, // empty unless tracing is enabled
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ContractCreation {
+	pub address: String,       // receipt.contract_address
+	pub bytecode_hash: String, // keccak256 of the deployed code
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccessListEntry {
+	pub address: String,
+	pub storage_keys: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -42,3 +73,22 @@ pub struct OtherTx {
 	pub nonce: u64,
 	pub index: u32, // transaction_index
 }
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct InternalTransaction {
+	pub index: u32,        // position within the transaction, depth first
+	pub call_type: String, // CALL, DELEGATECALL, STATICCALL, CREATE, CREATE2 or SELFDESTRUCT
+	pub from: String,
+	pub to: String,
+	pub value: String, // wei, uint256 in decimal
+	pub depth: u32,    // 1 for calls made directly by the transaction
+	pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EvmLog {
+	pub address: String,
+	pub topics: Vec<String>,
+	pub data: String,
+	pub log_index: u64,
+}
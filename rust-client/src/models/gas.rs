@@ -4,12 +4,13 @@ use serde::{Deserialize, Serialize};
 pub struct BlockGas {
 	pub gas_used: u64,
 	pub gas_limit: u64,
-	pub base_fee_per_gas: u64,
+	pub base_fee_per_gas: String, // wei, uint256 in decimal
+	pub burnt_fees: String, // base_fee_per_gas * gas_used in decimal
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct TransactionGas {
-	pub gas_price: u128,
+	pub gas_price: String, // effective gas price, uint256 in decimal
 	pub gas_used: u64,
-	pub total_gas_cost: u64,
+	pub total_gas_cost: String, // gas_used * gas_price in decimal
 }
@@ -5,6 +5,7 @@ pub mod event;
 pub mod extrinsic;
 pub mod gas;
 pub mod identity;
+pub mod session;
 pub mod staking;
 
 use sp_core::ConstU32;
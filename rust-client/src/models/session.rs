@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionValidators {
+	pub session_index: u32,
+	pub validators: Vec<String>,
+	pub queued_validators: Vec<String>,
+}
@@ -11,6 +11,16 @@ pub struct EraStaking {
 	pub validators: Vec<ValidatorDetail>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StakingRole {
+	pub stash: Option<String>,      // stash of the bond the account is part of
+	pub controller: Option<String>, // controller of that bond
+	pub is_validator: bool,
+	pub nominations: Vec<String>, // validators nominated by the stash
+	pub bonded: u128,
+	pub active: u128,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ValidatorDetail {
 	pub account: String,
@@ -1,8 +1,120 @@
-use sp_core::crypto::Ss58Codec;
+use ethers::{
+	providers::RpcError,
+	types::{U256, U512},
+	utils::format_units,
+};
 
-pub fn validate_ss58_address(address: &str) -> bool {
-	match sp_core::crypto::AccountId32::from_ss58check(address) {
-		Ok(_) => true,
-		Err(_) => false,
+/// Format a uint256 decimal string of base units, e.g. wei, with `decimals` for display.
+/// Trailing fractional zeros are trimmed, so `1500000000000000000` with 18 decimals is `1.5`.
+pub fn format_token_amount(raw: &str, decimals: u32) -> Option<String> {
+	let amount = U256::from_dec_str(raw).ok()?;
+	let formatted = format_units(amount, decimals).ok()?;
+
+	match formatted.split_once('.') {
+		Some((integer, fraction)) => {
+			let fraction = fraction.trim_end_matches('0');
+			if fraction.is_empty() {
+				Some(integer.to_string())
+			} else {
+				Some(format!("{}.{}", integer, fraction))
+			}
+		},
+		None => Some(formatted),
+	}
+}
+
+/// Sum of two uint256 decimal strings, `None` if either is invalid or the sum overflows
+pub fn add_token_amounts(a: &str, b: &str) -> Option<String> {
+	let sum = U256::from_dec_str(a).ok()?.checked_add(U256::from_dec_str(b).ok()?)?;
+	Some(sum.to_string())
+}
+
+/// Difference of two uint256 decimal strings, floored at zero for balances whose earlier
+/// history was not indexed
+pub fn sub_token_amounts(a: &str, b: &str) -> Option<String> {
+	let difference = U256::from_dec_str(a).ok()?.saturating_sub(U256::from_dec_str(b).ok()?);
+	Some(difference.to_string())
+}
+
+/// Split the fee of a transaction into the part burnt by EIP-1559 and the priority tip paid to
+/// the block author, as `(burnt, tip)`. Blocks without a base fee burn nothing.
+pub fn split_transaction_fee(
+	gas_used: U256,
+	effective_gas_price: U256,
+	base_fee_per_gas: U256,
+) -> (U512, U512) {
+	let base_fee = base_fee_per_gas.min(effective_gas_price);
+	let burnt = gas_used.full_mul(base_fee);
+	let tip = gas_used.full_mul(effective_gas_price - base_fee);
+	(burnt, tip)
+}
+
+/// Whether a JSON-RPC error means the node does not serve the method at all, as opposed to a
+/// failure such as a timeout that may not happen on the next call
+pub fn is_unsupported_method(error: &impl RpcError) -> bool {
+	error.as_error_response().is_some_and(|response| {
+		let message = response.message.to_lowercase();
+		response.code == -32601
+			|| message.contains("not supported")
+			|| message.contains("unsupported")
+			|| message.contains("does not exist")
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethers::providers::{HttpClientError, JsonRpcError};
+
+	#[test]
+	fn test_token_amount_arithmetic() {
+		assert_eq!(add_token_amounts("1", "2").as_deref(), Some("3"));
+		assert_eq!(add_token_amounts(&U256::MAX.to_string(), "1"), None);
+		assert_eq!(sub_token_amounts("5", "3").as_deref(), Some("2"));
+		assert_eq!(sub_token_amounts("3", "5").as_deref(), Some("0"));
+		assert_eq!(sub_token_amounts("abc", "5"), None);
+	}
+
+	#[test]
+	fn test_split_transaction_fee() {
+		let gwei = U256::exp10(9);
+		let (burnt, tip) = split_transaction_fee(U256::from(21_000), gwei * 12, gwei * 10);
+		assert_eq!(burnt, U512::from(21_000) * U512::from(gwei * 10));
+		assert_eq!(tip, U512::from(21_000) * U512::from(gwei * 2));
+
+		// Legacy blocks have no base fee, so everything goes to the author
+		let (burnt, tip) = split_transaction_fee(U256::from(21_000), gwei, U256::zero());
+		assert_eq!(burnt, U512::zero());
+		assert_eq!(tip, U512::from(21_000) * U512::from(gwei));
+	}
+
+	#[test]
+	fn test_format_token_amount() {
+		assert_eq!(format_token_amount("1500000000000000000", 18).as_deref(), Some("1.5"));
+		assert_eq!(format_token_amount("1000000", 6).as_deref(), Some("1"));
+		assert_eq!(format_token_amount("0", 18).as_deref(), Some("0"));
+		// Above u128::MAX
+		assert_eq!(
+			format_token_amount("340282366920938463463374607431768211456", 18).as_deref(),
+			Some("340282366920938463463.374607431768211456")
+		);
+		assert_eq!(format_token_amount("not a number", 18), None);
+	}
+
+	#[test]
+	fn test_is_unsupported_method() {
+		let error = |code, message: &str| {
+			HttpClientError::JsonRpcError(JsonRpcError {
+				code,
+				message: message.to_string(),
+				data: None,
+			})
+		};
+		assert!(is_unsupported_method(&error(-32601, "Method not found")));
+		assert!(is_unsupported_method(&error(
+			-32000,
+			"the method debug_traceTransaction does not exist"
+		)));
+		assert!(!is_unsupported_method(&error(-32000, "request timed out")));
 	}
 }